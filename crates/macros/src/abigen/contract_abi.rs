@@ -17,8 +17,15 @@
 //!    [{ .... }]
 //! "#);
 //!
-use starknet::core::types::contract::AbiEntry;
+//! Legacy (Cairo 0) ABIs are detected and converted into Sierra ABIs.
+use super::legacy_abi;
+use serde_json::Value;
+use starknet::core::types::contract::{AbiEntry, AbiEvent, TypedAbiEvent};
+use starknet::core::types::LegacyContractAbiEntry;
+use starknet_abigen_parser::abi_types::{AbiTypeAny, ParseError};
+use std::collections::HashMap;
 use std::fs::File;
+use std::path::{Path, PathBuf};
 use syn::{
     parse::{Parse, ParseStream, Result},
     Ident, LitStr, Token,
@@ -28,6 +35,8 @@ use syn::{
 pub(crate) struct ContractAbi {
     pub name: Ident,
     pub abi: Vec<AbiEntry>,
+    /// Names of the `flat` members/variants, indexed by event name.
+    pub flat_event_fields: HashMap<String, Vec<String>>,
 }
 
 impl Parse for ContractAbi {
@@ -35,15 +44,215 @@ impl Parse for ContractAbi {
         let name = input.parse::<Ident>()?;
         input.parse::<Token![,]>()?;
 
-        // Path rooted to the Cargo.toml location.
+        // Path relative to the current directory, or rooted
+        // to the Cargo.toml location.
         let json_path = input.parse::<LitStr>()?;
 
-        let abi =
-            serde_json::from_reader::<_, Vec<AbiEntry>>(File::open(json_path.value()).map_err(
-                |e| syn::Error::new(json_path.span(), format!("JSON open file error: {}", e)),
-            )?)
-            .map_err(|e| syn::Error::new(json_path.span(), format!("JSON parse error: {}", e)))?;
+        let mut abi_json = serde_json::from_reader::<_, Value>(
+            File::open(manifest_path(&json_path.value())).map_err(|e| {
+                syn::Error::new(json_path.span(), format!("JSON open file error: {}", e))
+            })?,
+        )
+        .map_err(|e| syn::Error::new(json_path.span(), format!("JSON parse error: {}", e)))?;
 
-        Ok(ContractAbi { name, abi })
+        let mut flat_event_fields = HashMap::new();
+        extract_flat_event_fields(&mut abi_json, &mut flat_event_fields);
+
+        let abi = if legacy_abi::is_legacy_abi(&abi_json) {
+            let legacy =
+                serde_json::from_value::<Vec<LegacyContractAbiEntry>>(abi_json).map_err(|e| {
+                    syn::Error::new(json_path.span(), format!("JSON parse error: {}", e))
+                })?;
+
+            legacy_abi::from_legacy_abi(&legacy).map_err(|e| {
+                syn::Error::new(json_path.span(), format!("Legacy ABI error: {}", e))
+            })?
+        } else {
+            serde_json::from_value::<Vec<AbiEntry>>(abi_json).map_err(|e| {
+                syn::Error::new(json_path.span(), format!("JSON parse error: {}", e))
+            })?
+        };
+
+        // Types are validated upfront to report malformed types
+        // as a compile error instead of panicking during expansion.
+        validate_types(&abi)
+            .map_err(|e| syn::Error::new(json_path.span(), format!("ABI type error: {}", e)))?;
+
+        Ok(ContractAbi {
+            name,
+            abi,
+            flat_event_fields,
+        })
+    }
+}
+
+/// Resolves the path of the ABI file. A relative path not found from the
+/// current directory is rooted to the Cargo.toml location of the crate
+/// invoking the macro, as the compiler is not running from this location
+/// when the crate is built as a dependency.
+fn manifest_path(path: &str) -> PathBuf {
+    let path = Path::new(path);
+
+    match std::env::var_os("CARGO_MANIFEST_DIR") {
+        Some(manifest_dir) if path.is_relative() && !path.exists() => {
+            Path::new(&manifest_dir).join(path)
+        }
+        _ => path.to_path_buf(),
+    }
+}
+
+/// Collects the event members/variants of `flat` kind, which is not
+/// supported by `starknet-core`. Those are rewritten as `nested` for the
+/// ABI to be deserialized, and the flat kind is restored once the
+/// events are parsed.
+fn extract_flat_event_fields(value: &mut Value, flat_fields: &mut HashMap<String, Vec<String>>) {
+    let Some(entries) = value.as_array_mut() else {
+        return;
+    };
+
+    for entry in entries {
+        match entry.get("type").and_then(Value::as_str) {
+            Some("interface") => {
+                if let Some(items) = entry.get_mut("items") {
+                    extract_flat_event_fields(items, flat_fields);
+                }
+            }
+            Some("event") => {
+                let name = entry
+                    .get("name")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string();
+
+                for list in ["members", "variants"] {
+                    let Some(fields) = entry.get_mut(list).and_then(Value::as_array_mut) else {
+                        continue;
+                    };
+
+                    for field in fields {
+                        if field.get("kind").and_then(Value::as_str) != Some("flat") {
+                            continue;
+                        }
+
+                        field["kind"] = Value::from("nested");
+
+                        if let Some(field_name) = field.get("name").and_then(Value::as_str) {
+                            flat_fields
+                                .entry(name.clone())
+                                .or_default()
+                                .push(field_name.to_string());
+                        }
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Parses every type string found in the ABI entries,
+/// returning the first parsing error, if any.
+fn validate_types(abi: &[AbiEntry]) -> std::result::Result<(), ParseError> {
+    for entry in abi {
+        let mut types: Vec<&str> = vec![];
+
+        match entry {
+            AbiEntry::Function(f) | AbiEntry::L1Handler(f) => {
+                types.extend(f.inputs.iter().map(|i| i.r#type.as_str()));
+                types.extend(f.outputs.iter().map(|o| o.r#type.as_str()));
+            }
+            AbiEntry::Constructor(c) => {
+                types.extend(c.inputs.iter().map(|i| i.r#type.as_str()));
+            }
+            AbiEntry::Struct(s) => {
+                types.push(&s.name);
+                types.extend(s.members.iter().map(|m| m.r#type.as_str()));
+            }
+            AbiEntry::Enum(e) => {
+                types.push(&e.name);
+                types.extend(e.variants.iter().map(|v| v.r#type.as_str()));
+            }
+            AbiEntry::Event(AbiEvent::Typed(TypedAbiEvent::Struct(s))) => {
+                types.push(&s.name);
+                types.extend(s.members.iter().map(|m| m.r#type.as_str()));
+            }
+            AbiEntry::Event(AbiEvent::Typed(TypedAbiEvent::Enum(e))) => {
+                types.push(&e.name);
+                types.extend(e.variants.iter().map(|v| v.r#type.as_str()));
+            }
+            AbiEntry::Event(AbiEvent::Untyped(e)) => {
+                types.extend(e.inputs.iter().map(|i| i.r#type.as_str()));
+            }
+            AbiEntry::Interface(interface) => validate_types(&interface.items)?,
+            _ => (),
+        }
+
+        for t in types {
+            AbiTypeAny::try_from_string(t)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manifest_path() {
+        let manifest_dir = PathBuf::from(std::env::var_os("CARGO_MANIFEST_DIR").unwrap());
+
+        // Tests are running from the crate directory.
+        assert_eq!(manifest_path("Cargo.toml"), PathBuf::from("Cargo.toml"));
+
+        assert_eq!(
+            manifest_path("not/found.json"),
+            manifest_dir.join("not/found.json")
+        );
+        assert_eq!(
+            manifest_path("/abs/abi.json"),
+            PathBuf::from("/abs/abi.json")
+        );
+    }
+
+    #[test]
+    fn test_validate_types_untyped_event() {
+        let abi: Vec<AbiEntry> = serde_json::from_str(
+            r#"[{"type":"event","name":"Paused","inputs":[
+                {"name":"a","type":"core::felt252"},
+                {"name":"b","type":"core::array::Array::<core::felt252"}
+            ]}]"#,
+        )
+        .unwrap();
+
+        assert_eq!(validate_types(&abi).unwrap_err().expected, "'>'");
+    }
+
+    #[test]
+    fn test_extract_flat_event_fields() {
+        let mut abi: Value = serde_json::from_str(
+            r#"[
+            {"type":"event","name":"c::Event","kind":"enum","variants":[
+                {"name":"OwnableEvent","type":"o::Ownable::Event","kind":"flat"},
+                {"name":"Transfer","type":"c::Transfer","kind":"nested"}
+            ]},
+            {"type":"interface","name":"c::I","items":[
+                {"type":"event","name":"c::S","kind":"struct","members":[
+                    {"name":"inner","type":"o::Ownable::Event","kind":"flat"}
+                ]}
+            ]}
+        ]"#,
+        )
+        .unwrap();
+
+        let mut flat_fields = HashMap::new();
+        extract_flat_event_fields(&mut abi, &mut flat_fields);
+
+        assert_eq!(flat_fields.len(), 2);
+        assert_eq!(flat_fields["c::Event"], vec!["OwnableEvent".to_string()]);
+        assert_eq!(flat_fields["c::S"], vec!["inner".to_string()]);
+        assert_eq!(abi[0]["variants"][0]["kind"], "nested");
+        assert_eq!(abi[1]["items"][0]["members"][0]["kind"], "nested");
     }
 }
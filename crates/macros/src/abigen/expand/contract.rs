@@ -2,10 +2,16 @@
 //! default configuration for provider and account, if any.
 use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
+use starknet_abigen_parser::abi_types::AbiTypeAny;
 use syn::Ident;
 
+use super::function::{expand_calldata, get_func_inputs};
 use super::utils;
 
+/// Arguments of the deployment functions, which can't be
+/// used as constructor arguments names.
+const DEPLOY_ARGS: [&str; 4] = ["account", "class_hash", "salt", "unique"];
+
 pub struct CairoContract;
 
 impl CairoContract {
@@ -93,4 +99,138 @@ impl CairoContract {
 
         q
     }
+
+    /// Expands the events related functions of the reader,
+    /// for the given contract `Event` enum.
+    pub fn expand_events(contract_name: Ident, event: Ident) -> TokenStream2 {
+        let reader = utils::str_to_ident(format!("{}Reader", contract_name).as_str());
+
+        quote! {
+            impl<'a, P: starknet::providers::Provider + Sync> #reader<'a, P> {
+                /// Streams the events emitted by the contract between the given blocks,
+                /// decoded into the contract events. The events are fetched by pages of
+                /// `chunk_size` events, and can be filtered with the keys built by the
+                /// events filters.
+                pub fn events_stream(
+                    &self,
+                    from_block: starknet::core::types::BlockId,
+                    to_block: starknet::core::types::BlockId,
+                    keys: Option<Vec<Vec<starknet::core::types::FieldElement>>>,
+                    chunk_size: u64,
+                ) -> impl starknet_abigen_parser::event_stream::Stream<
+                    Item = Result<
+                        starknet_abigen_parser::event_stream::DecodedEvent<#event>,
+                        starknet_abigen_parser::event_stream::EventStreamError,
+                    >,
+                > + 'a {
+                    let filter = starknet::core::types::EventFilter {
+                        from_block: Some(from_block),
+                        to_block: Some(to_block),
+                        address: Some(self.address),
+                        keys,
+                    };
+
+                    starknet_abigen_parser::event_stream::event_stream(self.provider, filter, chunk_size)
+                }
+
+                /// Decodes the events emitted by the contract in the given receipt,
+                /// in the order of emission. Events of other contracts are ignored.
+                pub fn events_from_receipt(
+                    &self,
+                    receipt: &starknet::core::types::MaybePendingTransactionReceipt,
+                ) -> Result<Vec<#event>, starknet_abigen_parser::EventError> {
+                    starknet_abigen_parser::receipt::decode_receipt_events(receipt, self.address)
+                }
+
+                /// Fetches the receipt of the given transaction and decodes the events
+                /// emitted by the contract, in the order of emission.
+                pub async fn events_from_tx(
+                    &self,
+                    transaction_hash: starknet::core::types::FieldElement,
+                ) -> Result<Vec<#event>, starknet_abigen_parser::receipt::ReceiptEventsError> {
+                    use starknet::providers::Provider;
+
+                    let receipt = self.provider.get_transaction_receipt(transaction_hash).await?;
+                    Ok(self.events_from_receipt(&receipt)?)
+                }
+            }
+        }
+    }
+
+    /// Expands the deployment functions of the contract, using the given
+    /// constructor inputs to build the constructor calldata.
+    /// The contract is deployed through the Universal Deployer Contract (UDC).
+    pub fn expand_constructor(
+        contract_name: Ident,
+        inputs: &[(String, AbiTypeAny)],
+    ) -> TokenStream2 {
+        // Constructor arguments sharing their name with the deployment
+        // arguments are suffixed.
+        let inputs: Vec<(String, AbiTypeAny)> = inputs
+            .iter()
+            .map(|(name, abi_type)| {
+                if DEPLOY_ARGS.contains(&name.as_str()) {
+                    (format!("{}_", name), abi_type.clone())
+                } else {
+                    (name.clone(), abi_type.clone())
+                }
+            })
+            .collect();
+
+        let decl_inputs = get_func_inputs(&inputs);
+        let names: Vec<Ident> = inputs.iter().map(|(n, _)| utils::str_to_ident(n)).collect();
+        let calldata = expand_calldata(&inputs);
+
+        quote! {
+            impl<A: starknet::accounts::ConnectedAccount + Sync> #contract_name<A> {
+                /// Serializes the constructor arguments.
+                #[allow(clippy::ptr_arg)]
+                fn constructor_calldata(#(#decl_inputs),*) -> Vec<starknet::core::types::FieldElement> {
+                    use starknet_abigen_parser::CairoType;
+
+                    #calldata
+                    __calldata
+                }
+
+                /// Computes the address of the contract deployed by `account` through the UDC,
+                /// without sending any transaction.
+                #[allow(clippy::ptr_arg)]
+                pub fn deployed_address(
+                    account: &A,
+                    class_hash: starknet::core::types::FieldElement,
+                    salt: starknet::core::types::FieldElement,
+                    unique: bool,
+                    #(#decl_inputs),*
+                ) -> starknet::core::types::FieldElement {
+                    let factory = starknet::contract::ContractFactory::new(class_hash, account);
+                    factory
+                        .deploy(Self::constructor_calldata(#(#names),*), salt, unique)
+                        .deployed_address()
+                }
+
+                /// Deploys the contract of class `class_hash` through the UDC with the
+                /// given constructor arguments, returning the contract instance at the
+                /// deployed address and the deployment transaction.
+                #[allow(clippy::ptr_arg)]
+                pub async fn deploy(
+                    account: A,
+                    class_hash: starknet::core::types::FieldElement,
+                    salt: starknet::core::types::FieldElement,
+                    unique: bool,
+                    #(#decl_inputs),*
+                ) -> Result<
+                    (Self, starknet::core::types::InvokeTransactionResult),
+                    starknet::accounts::AccountError<A::SignError>,
+                > {
+                    let (address, tx) = {
+                        let factory = starknet::contract::ContractFactory::new(class_hash, &account);
+                        let deployment = factory.deploy(Self::constructor_calldata(#(#names),*), salt, unique);
+                        (deployment.deployed_address(), deployment.send().await?)
+                    };
+
+                    Ok((Self::new(address, account), tx))
+                }
+            }
+        }
+    }
 }
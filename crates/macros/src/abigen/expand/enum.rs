@@ -59,6 +59,7 @@ impl Expandable for CairoEnum {
 
         for (i, (name, abi_type)) in self.variants.iter().enumerate() {
             let variant_name = str_to_ident(name);
+            let segment = format!("::{}", name);
             let ty = str_to_type(&abi_type.to_rust_type_path());
 
             // Tuples type used as rust type item path must be surrounded
@@ -70,10 +71,10 @@ impl Expandable for CairoEnum {
 
             if abi_type.is_unit() {
                 serializations.push(quote! {
-                    #enum_name::#variant_name => usize::serialize(&#i)
+                    #enum_name::#variant_name => usize::serialize_to(&#i, __out)
                 });
                 deserializations.push(quote! {
-                    #i => Ok(#enum_name::#variant_name)
+                    #i => Ok((#enum_name::#variant_name, 1))
                 });
                 serialized_sizes.push(quote! {
                     #enum_name::#variant_name => 1
@@ -81,14 +82,17 @@ impl Expandable for CairoEnum {
             } else {
                 serializations.push(quote! {
                     #enum_name::#variant_name(val) => {
-                        let mut temp = vec![];
-                        temp.extend(usize::serialize(&#i));
-                        temp.extend(#ty_punctuated::serialize(val));
-                        temp
+                        usize::serialize_to(&#i, __out);
+                        #ty_punctuated::serialize_to(val, __out);
                     }
                 });
                 deserializations.push(quote! {
-                    #i => Ok(#enum_name::#variant_name(#ty_punctuated::deserialize(__felts, __offset + 1)?))
+                    #i => {
+                        let (__val, __size) = #ty_punctuated::deserialize_with_size(__felts, __offset + 1)
+                            .map_err(|e| e.with_context(#name_str, #segment, __offset + 1))?;
+                        // +1 because of the variant index.
+                        Ok((#enum_name::#variant_name(__val), __size + 1))
+                    }
                 });
                 // +1 because we have to handle the variant index also.
                 serialized_sizes.push(quote! {
@@ -98,7 +102,8 @@ impl Expandable for CairoEnum {
         }
 
         deserializations.push(quote! {
-            _ => panic!("Index not handle for enum {}", #name_str)
+            _ => Err(starknet_abigen_parser::cairo_types::Error::Deserialize(
+                format!("Index {} not handled for enum {}", __index, #name_str)))
         });
 
         let gentys: Vec<Ident> = self.get_gentys().iter().map(|g| str_to_ident(g)).collect();
@@ -132,14 +137,20 @@ impl Expandable for CairoEnum {
                 }
 
                 fn serialize(__rust: &Self::RustType) -> Vec<starknet::core::types::FieldElement> {
+                    let mut __out = Vec::with_capacity(Self::serialized_size(__rust));
+                    Self::serialize_to(__rust, &mut __out);
+                    __out
+                }
+
+                fn serialize_to(__rust: &Self::RustType, __out: &mut Vec<starknet::core::types::FieldElement>) {
                     match __rust {
                         #(#serializations),*
                     }
                 }
 
-                fn deserialize(__felts: &[starknet::core::types::FieldElement], __offset: usize) -> starknet_abigen_parser::cairo_types::Result<Self::RustType> {
-                    let __index:u128 = __felts[__offset].try_into().unwrap();
-                    match __index as usize {
+                fn deserialize_with_size(__felts: &[starknet::core::types::FieldElement], __offset: usize) -> starknet_abigen_parser::cairo_types::Result<(Self::RustType, usize)> {
+                    let __index = usize::deserialize(__felts, __offset)?;
+                    match __index {
                         #(#deserializations),*
                     }
 
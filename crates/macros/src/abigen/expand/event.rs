@@ -1,17 +1,27 @@
 //! Events expansion.
+//!
+//! Events are serialized into keys and data:
+//!
+//! * Struct events serialize their `key` members into the keys,
+//!   and other members into the data. `nested` and `flat` members
+//!   are events themselves, and are serialized recursively.
+//! * Enum events add the selector of the variant name to the keys
+//!   for `nested` variants, before serializing the variant event.
+//!   `flat` variants are serialized without selector, the variant
+//!   event being an enum which adds it's own selector.
 use super::{
     utils::{str_to_ident, str_to_litstr, str_to_type},
     Expandable, ExpandableEvent,
 };
 
-use starknet::core::types::contract::EventFieldKind;
 use starknet_abigen_parser::{
     abi_types::{AbiType, AbiTypeAny},
-    CairoEvent, CairoEventInner,
+    CairoEnum, CairoEvent, CairoEventInner, CairoStruct, EventFieldKind,
 };
 
 use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
+use syn::LitStr;
 
 impl ExpandableEvent for CairoEvent {
     fn expand_decl(&self) -> TokenStream2 {
@@ -34,167 +44,453 @@ impl ExpandableEvent for CairoEvent {
 
         // Generate the get_selector() method for this event.
         let name_ident = str_to_ident(&self.get_name());
-        let name_str = str_to_litstr(&self.get_name());
-        let selector = quote! {
+        // The selector is always computed from the cairo name, even if
+        // an alias is used for the rust type.
+        let name_str = str_to_litstr(&self.get_cairo_name());
+
+        let deser = match &self.inner {
+            CairoEventInner::Struct(s) => expand_struct_deserialize_event(self, s),
+            CairoEventInner::Enum(e) => expand_enum_deserialize_event(self, e, events),
+        };
+
+        tokens.push(quote! {
             impl #name_ident {
                 pub fn get_selector() -> starknet::core::types::FieldElement {
                     starknet::macros::selector!(#name_str)
                 }
+
+                #deser
             }
-        };
+        });
+
+        if let CairoEventInner::Struct(s) = &self.inner {
+            tokens.push(expand_struct_event_filter(self, s, events));
 
-        tokens.push(selector);
+            // The struct is encoded with the selector of the first variant it's
+            // reached through. Events of components are also preceded by the selector
+            // of the component variant, and must be encoded from the contract `Event`.
+            let selector_name = str_to_litstr(&variant_selector_names(self, events)[0]);
+            let encode = expand_encode(quote! {
+                let mut keys = vec![starknet::macros::selector!(#selector_name)];
+                let mut data = vec![];
+                self.serialize_event(&mut keys, &mut data);
+                (keys, data)
+            });
 
-        // Stop here if it's not the Event enum.
-        if self.get_name() != "Event" {
+            tokens.push(quote! {
+                impl #name_ident {
+                    #encode
+                }
+            });
+        }
+
+        // Stop here if it's not an Event enum.
+        if self.get_cairo_name() != "Event" {
             return quote! {
                 #(#tokens)*
             };
         }
 
-        // If it's the Event enum, we can generate the TryFrom<EmittedEvent>.
-
-        // It should always be an enum here.
-        if let CairoEventInner::Enum(inner) = &self.inner {
-            let mut variants_tokens = vec![];
-
-            for (v_name, _) in &inner.variants {
-                // Get the corresponding CairoEvent in the array to access it's fields.
-                let cev = events
-                    .iter()
-                    .find(|&e| &e.get_name() == v_name)
-                    .unwrap_or_else(|| panic!("Event variant {} was not found in events", v_name));
-
-                let _cev_fields_kinds = cev.count_fields_kinds();
-
-                let mut desers_tokens = vec![];
-                let mut names_tokens = vec![];
-                let v_ident = str_to_ident(v_name);
-                let v_name_str = str_to_litstr(v_name);
-
-                // Let's write the deserialization of each member/variants
-                // of the current event.
-                match &cev.inner {
-                    CairoEventInner::Struct(s) => {
-                        for (idx, (name, abi_type)) in s.members.iter().enumerate() {
-                            let kind = &cev.fields_kinds[idx];
-                            let name_str = str_to_litstr(name);
-                            let name = str_to_ident(name);
-                            let ty = str_to_type(&abi_type.to_rust_type_path());
-                            let ty_punctuated = match abi_type {
-                                AbiTypeAny::Tuple(_) => quote!(<#ty>),
-                                _ => quote!(#ty),
-                            };
-
-                            match kind {
-                                EventFieldKind::Key => {
-                                    desers_tokens.push(quote! {
-                                        let #name = match #ty_punctuated::deserialize(&event.keys, key_offset) {
-                                            Ok(v) => v,
-                                            Err(e) => return Err(format!("Could not deserialize field {} for {}: {:?}", #name_str, #v_name_str, e)),
-                                        };
-                                        key_offset += #ty_punctuated::serialized_size(&#name);
-                                    });
-                                }
-                                EventFieldKind::Data => {
-                                    desers_tokens.push(quote! {
-                                        let #name = match #ty_punctuated::deserialize(&event.data, data_offset) {
-                                            Ok(v) => v,
-                                            Err(e) => return Err(format!("Could not deserialize field {} for {}: {:?}", #name_str, #v_name_str, e)),
-                                        };
-                                        data_offset += #ty_punctuated::serialized_size(&#name);
-                                    });
-                                }
-                                _ => {}
-                            };
-
-                            names_tokens.push(quote!(#name));
-                        }
+        // If it's an Event enum, we can generate the decode function
+        // and the TryFrom<EmittedEvent> and TryFrom<Event>, which are lenient.
+        if let CairoEventInner::Enum(_) = &self.inner {
+            let rust_name_str = str_to_litstr(&self.get_name());
+            let encode = expand_encode(quote! {
+                let mut keys = vec![];
+                let mut data = vec![];
+                self.serialize_event(&mut keys, &mut data);
+                (keys, data)
+            });
+
+            let try_from = quote! {
+                impl #name_ident {
+                    /// Decodes the event from it's keys and data.
+                    /// In strict mode, all the keys and data must be consumed.
+                    pub fn decode(
+                        keys: &[starknet::core::types::FieldElement],
+                        data: &[starknet::core::types::FieldElement],
+                        mode: starknet_abigen_parser::EventDecodingMode,
+                    ) -> Result<Self, starknet_abigen_parser::EventError> {
+                        let (ev, key_offset, data_offset) = #name_ident::deserialize_event(keys, data, 0, 0)?;
+                        mode.check_consumed(#rust_name_str, keys, key_offset, data, data_offset)?;
+                        Ok(ev)
+                    }
+
+                    #encode
+                }
+
+                impl TryFrom<starknet::core::types::EmittedEvent> for #name_ident {
+                    type Error = starknet_abigen_parser::EventError;
+
+                    fn try_from(event: starknet::core::types::EmittedEvent) -> Result<Self, Self::Error> {
+                        #name_ident::decode(&event.keys, &event.data, starknet_abigen_parser::EventDecodingMode::Lenient)
                     }
-                    CairoEventInner::Enum(e) => {
-                        for (idx, (name, abi_type)) in e.variants.iter().enumerate() {
-                            let kind = &cev.fields_kinds[idx];
-                            let name_str = str_to_litstr(name);
-                            let name = str_to_ident(name);
-                            let ty = str_to_type(&abi_type.to_rust_type_path());
-                            let ty_punctuated = match abi_type {
-                                AbiTypeAny::Tuple(_) => quote!(<#ty>),
-                                _ => quote!(#ty),
-                            };
-
-                            match kind {
-                                EventFieldKind::Key => {
-                                    desers_tokens.push(quote! {
-                                        let #name = match #ty_punctuated::deserialize(&event.keys, key_offset) {
-                                            Ok(v) => v,
-                                            Err(e) => return Err(format!("Could not deserialize field {} for {}: {:?}", #name_str, #v_name_str, e)),
-                                        };
-                                        key_offset += #ty_punctuated::serialized_size(&#name);
-                                    });
-                                }
-                                EventFieldKind::Data => {
-                                    desers_tokens.push(quote! {
-                                        let #name = match #ty_punctuated::deserialize(&event.data, data_offset) {
-                                            Ok(v) => v,
-                                            Err(e) => return Err(format!("Could not deserialize field {} for {}: {:?}", #name_str, #v_name_str, e)),
-                                        };
-                                        data_offset += #ty_punctuated::serialized_size(&#name);
-                                    });
-                                }
-                                _ => {}
-                            };
-
-                            names_tokens.push(quote!(#name));
-                        }
+                }
+
+                impl TryFrom<starknet::core::types::Event> for #name_ident {
+                    type Error = starknet_abigen_parser::EventError;
+
+                    fn try_from(event: starknet::core::types::Event) -> Result<Self, Self::Error> {
+                        #name_ident::decode(&event.keys, &event.data, starknet_abigen_parser::EventDecodingMode::Lenient)
                     }
+                }
+            };
+
+            tokens.push(try_from);
+        }
+
+        quote! {
+            #(#tokens)*
+        }
+    }
+}
+
+/// Expands the `encode` and `to_emitted_event` functions of an event,
+/// `encode_body` returning the keys and data of the event.
+fn expand_encode(encode_body: TokenStream2) -> TokenStream2 {
+    quote! {
+        /// Encodes the event into it's keys and data.
+        pub fn encode(&self) -> (Vec<starknet::core::types::FieldElement>, Vec<starknet::core::types::FieldElement>) {
+            #encode_body
+        }
+
+        /// Encodes the event into an `EmittedEvent`, as returned by the provider.
+        pub fn to_emitted_event(
+            &self,
+            from_address: starknet::core::types::FieldElement,
+            block_hash: starknet::core::types::FieldElement,
+            block_number: u64,
+            transaction_hash: starknet::core::types::FieldElement,
+        ) -> starknet::core::types::EmittedEvent {
+            let (keys, data) = self.encode();
+
+            starknet::core::types::EmittedEvent {
+                from_address,
+                keys,
+                data,
+                block_hash,
+                block_number,
+                transaction_hash,
+            }
+        }
+    }
+}
+
+/// Returns the names of the enum variants a struct event is reached through,
+/// which may be named differently than the struct. The struct name is used
+/// if the event is not a variant of any enum.
+fn variant_selector_names(cev: &CairoEvent, events: &[CairoEvent]) -> Vec<String> {
+    let mut selector_names: Vec<String> = vec![];
+    for ev in events {
+        if let CairoEventInner::Enum(e) = &ev.inner {
+            for (v_name, v_abi) in &e.variants {
+                if v_abi.get_cairo_type_full() == cev.abi.get_cairo_type_full()
+                    && !selector_names.contains(v_name)
+                {
+                    selector_names.push(v_name.clone());
+                }
+            }
+        }
+    }
+
+    if selector_names.is_empty() {
+        selector_names.push(cev.get_cairo_name());
+    }
+
+    selector_names
+}
+
+/// Expands the `deserialize_event` function of a struct event, which deserializes
+/// each member from the keys or the data depending on it's kind.
+fn expand_struct_deserialize_event(cev: &CairoEvent, s: &CairoStruct) -> TokenStream2 {
+    let ev_name_str = str_to_litstr(&cev.get_name());
+    let mut desers_tokens = vec![];
+    let mut sers_tokens = vec![];
+    let mut names_tokens = vec![];
+
+    for (idx, (name, abi_type)) in s.members.iter().enumerate() {
+        let kind = &cev.fields_kinds[idx];
+        let name_str = str_to_litstr(name);
+        let name = str_to_ident(name);
+        let ty = str_to_type(&abi_type.to_rust_type_path());
+
+        let (felts, offset) = match kind {
+            EventFieldKind::Key => (quote!(__keys), quote!(__key_offset)),
+            EventFieldKind::Data => (quote!(__data), quote!(__data_offset)),
+            EventFieldKind::Nested | EventFieldKind::Flat => {
+                // The member is an event, which handles itself keys and data.
+                let ty_punctuated = match abi_type {
+                    AbiTypeAny::Tuple(_) => quote!(<#ty>),
+                    _ => quote!(#ty),
                 };
 
-                let variant = quote! {
-                    if selector == #v_ident::get_selector() {
-                        // TODO: add a validation to check keys len and data len.
-                        // To have a nice error message if the event is not formatted as
-                        // expected.
+                desers_tokens.push(quote! {
+                    let (#name, __k, __d) = #ty_punctuated::deserialize_event(__keys, __data, __key_offset, __data_offset)?;
+                    __key_offset = __k;
+                    __data_offset = __d;
+                });
+                sers_tokens.push(quote!(self.#name.serialize_event(__keys, __data);));
+                names_tokens.push(quote!(#name));
+                continue;
+            }
+        };
+
+        desers_tokens.push(quote! {
+            let (#name, __size) = <#ty as starknet_abigen_parser::CairoType>::deserialize_with_size(#felts, #offset)
+                .map_err(|e| starknet_abigen_parser::EventError::Field {
+                    event: #ev_name_str.to_string(),
+                    field: #name_str.to_string(),
+                    source: e,
+                })?;
+            #offset += __size;
+        });
+        sers_tokens.push(quote! {
+            <#ty as starknet_abigen_parser::CairoType>::serialize_to(&self.#name, #felts);
+        });
+        names_tokens.push(quote!(#name));
+    }
+
+    let name_ident = str_to_ident(&cev.get_name());
+
+    quote! {
+        /// Deserializes the event from the keys and data, starting at the given offsets.
+        /// Returns the event, and the keys and data offsets after the event.
+        #[allow(unused_mut)]
+        pub fn deserialize_event(
+            __keys: &[starknet::core::types::FieldElement],
+            __data: &[starknet::core::types::FieldElement],
+            __key_offset: usize,
+            __data_offset: usize,
+        ) -> Result<(Self, usize, usize), starknet_abigen_parser::EventError> {
+            let mut __key_offset = __key_offset;
+            let mut __data_offset = __data_offset;
+
+            #(#desers_tokens)*
+
+            Ok((#name_ident {
+                #(#names_tokens),*
+            }, __key_offset, __data_offset))
+        }
+
+        /// Serializes the event, appending it's members to the keys or data
+        /// depending on their kind.
+        #[allow(unused_variables)]
+        pub fn serialize_event(
+            &self,
+            __keys: &mut Vec<starknet::core::types::FieldElement>,
+            __data: &mut Vec<starknet::core::types::FieldElement>,
+        ) {
+            #(#sers_tokens)*
+        }
+    }
+}
 
-                        // We skip the selector.
-                        let mut key_offset = 1;
-                        let mut data_offset = 0;
+/// Expands the keys filter builder of a struct event, with one setter
+/// for each `key` member.
+fn expand_struct_event_filter(
+    cev: &CairoEvent,
+    s: &CairoStruct,
+    events: &[CairoEvent],
+) -> TokenStream2 {
+    // The event is emitted with the selector of the enum variant(s) it's
+    // reached through.
+    let selector_names: Vec<LitStr> = variant_selector_names(cev, events)
+        .iter()
+        .map(|n| str_to_litstr(n))
+        .collect();
+    let filter_ident = str_to_ident(&format!("{}Filter", cev.get_name()));
 
-                        #(#desers_tokens)*
+    let mut fields = vec![];
+    let mut setters = vec![];
+    let mut keys_tokens = vec![];
 
-                        return Ok(Event::#v_ident(#v_ident {
-                            #(#names_tokens),*
-                        }))
+    for (idx, (name, abi_type)) in s.members.iter().enumerate() {
+        let name = str_to_ident(name);
+        let ty = str_to_type(&abi_type.to_rust_type_path());
+        let rust_ty = str_to_type(&abi_type.to_rust_type());
+
+        match cev.fields_kinds[idx] {
+            EventFieldKind::Key => {
+                fields.push(quote!(#name: Option<Vec<starknet::core::types::FieldElement>>));
+                setters.push(quote! {
+                    /// Only matches the events with the given key value.
+                    #[allow(clippy::ptr_arg)]
+                    pub fn #name(mut self, #name: &#rust_ty) -> Self {
+                        self.#name = Some(<#ty as starknet_abigen_parser::CairoType>::serialize(#name));
+                        self
+                    }
+                });
+                keys_tokens.push(quote! {
+                    match &self.#name {
+                        Some(felts) => keys.extend(felts.iter().map(|f| vec![*f])),
+                        None => match <#ty as starknet_abigen_parser::CairoType>::SERIALIZED_SIZE {
+                            Some(size) => keys.extend(std::iter::repeat(vec![]).take(size)),
+                            // The position of the next keys can't be known.
+                            None => return Self::trim(keys),
+                        },
                     };
+                });
+            }
+            EventFieldKind::Data => (),
+            // The keys of a nested event can't be known.
+            EventFieldKind::Nested | EventFieldKind::Flat => break,
+        }
+    }
+
+    quote! {
+        /// Builder of the keys used to filter the events, where each
+        /// key is a list of accepted values (an empty list accepting any value).
+        #[derive(Debug, Clone, Default)]
+        pub struct #filter_ident {
+            selectors: Option<Vec<starknet::core::types::FieldElement>>,
+            #(#fields),*
+        }
+
+        impl #filter_ident {
+            pub fn new() -> Self {
+                Self::default()
+            }
+
+            /// Overrides the selectors preceding the event keys, which default
+            /// to the selector of the event variant. Nested events of components
+            /// are, for instance, preceded by the selector of the component variant.
+            pub fn selectors(mut self, selectors: Vec<starknet::core::types::FieldElement>) -> Self {
+                self.selectors = Some(selectors);
+                self
+            }
+
+            #(#setters)*
+
+            /// Builds the keys to be given to the `EventFilter`.
+            pub fn build(&self) -> Vec<Vec<starknet::core::types::FieldElement>> {
+                let mut keys: Vec<Vec<starknet::core::types::FieldElement>> = match &self.selectors {
+                    Some(selectors) => selectors.iter().map(|s| vec![*s]).collect(),
+                    None => vec![vec![#(starknet::macros::selector!(#selector_names)),*]],
                 };
 
-                variants_tokens.push(variant);
+                #(#keys_tokens)*
+
+                Self::trim(keys)
             }
 
-            // TODO: change for custom type instead of str for error?
-            let try_from = quote! {
-                impl TryFrom<starknet::core::types::EmittedEvent> for Event {
-                    type Error = String;
+            /// Removes the trailing keys accepting any value.
+            fn trim(
+                mut keys: Vec<Vec<starknet::core::types::FieldElement>>,
+            ) -> Vec<Vec<starknet::core::types::FieldElement>> {
+                while matches!(keys.last(), Some(k) if k.is_empty()) {
+                    keys.pop();
+                }
 
-                    fn try_from(event: starknet::core::types::EmittedEvent) -> Result<Self, Self::Error> {
-                        use starknet_abigen_parser::CairoType;
+                keys
+            }
+        }
+    }
+}
 
-                        if event.keys.is_empty() {
-                            return Err("Missing event selector, no keys found".to_string());
-                        }
-                        let selector = event.keys[0];
+/// Expands the `has_selector` and `deserialize_event` functions of an enum event,
+/// which dispatch on the selector found in the keys to deserialize the variant.
+fn expand_enum_deserialize_event(
+    cev: &CairoEvent,
+    e: &CairoEnum,
+    events: &[CairoEvent],
+) -> TokenStream2 {
+    let name_ident = str_to_ident(&cev.get_name());
+    let ev_name_str = str_to_litstr(&cev.get_name());
 
-                        #(#variants_tokens)*
+    let mut selectors_checks = vec![];
+    let mut variants_tokens = vec![];
+    let mut sers_tokens = vec![];
 
-                        Err(format!("Could not match any event from selector {:#064x}", selector))
-                    }
+    for (idx, (v_name, v_abi)) in e.variants.iter().enumerate() {
+        // Get the corresponding CairoEvent in the array to access it's kind.
+        let v_cev = events
+            .iter()
+            .find(|&e| e.abi.get_cairo_type_full() == v_abi.get_cairo_type_full())
+            .unwrap_or_else(|| panic!("Event variant {} was not found in events", v_name));
+
+        let v_ident = str_to_ident(v_name);
+        let v_name_str = str_to_litstr(v_name);
+        let v_ty = str_to_ident(&v_cev.get_name());
+
+        // Only enums can be flattened, as a struct event has no selector
+        // to be distinguished from the other variants.
+        let is_flat = cev.fields_kinds[idx] == EventFieldKind::Flat
+            && matches!(v_cev.inner, CairoEventInner::Enum(_));
+
+        if is_flat {
+            selectors_checks.push(quote!(#v_ty::has_selector(selector)));
+            variants_tokens.push(quote! {
+                if #v_ty::has_selector(__selector) {
+                    let (ev, __k, __d) = #v_ty::deserialize_event(__keys, __data, __key_offset, __data_offset)?;
+                    return Ok((#name_ident::#v_ident(ev), __k, __d));
+                }
+            });
+            sers_tokens.push(quote! {
+                #name_ident::#v_ident(ev) => ev.serialize_event(__keys, __data)
+            });
+        } else {
+            selectors_checks.push(quote!(selector == starknet::macros::selector!(#v_name_str)));
+            variants_tokens.push(quote! {
+                if __selector == starknet::macros::selector!(#v_name_str) {
+                    // We skip the selector.
+                    let (ev, __k, __d) = #v_ty::deserialize_event(__keys, __data, __key_offset + 1, __data_offset)?;
+                    return Ok((#name_ident::#v_ident(ev), __k, __d));
                 }
+            });
+            sers_tokens.push(quote! {
+                #name_ident::#v_ident(ev) => {
+                    __keys.push(starknet::macros::selector!(#v_name_str));
+                    ev.serialize_event(__keys, __data);
+                }
+            });
+        }
+    }
+
+    let has_selector = if selectors_checks.is_empty() {
+        quote!(false)
+    } else {
+        quote!(#(#selectors_checks)||*)
+    };
+
+    quote! {
+        /// Returns true if the selector matches one of the variants,
+        /// including the variants of flattened events.
+        pub fn has_selector(selector: starknet::core::types::FieldElement) -> bool {
+            #has_selector
+        }
+
+        /// Deserializes the event from the keys and data, starting at the given offsets.
+        /// The key at `key_offset` is expected to be the selector of a variant.
+        /// Returns the event, and the keys and data offsets after the event.
+        pub fn deserialize_event(
+            __keys: &[starknet::core::types::FieldElement],
+            __data: &[starknet::core::types::FieldElement],
+            __key_offset: usize,
+            __data_offset: usize,
+        ) -> Result<(Self, usize, usize), starknet_abigen_parser::EventError> {
+            let __selector = match __keys.get(__key_offset) {
+                Some(s) => *s,
+                None => return Err(starknet_abigen_parser::EventError::MissingKeys {
+                    event: #ev_name_str.to_string(),
+                    offset: __key_offset,
+                }),
             };
 
-            tokens.push(try_from);
+            #(#variants_tokens)*
+
+            Err(starknet_abigen_parser::EventError::UnknownSelector { selector: __selector })
         }
 
-        quote! {
-            #(#tokens)*
+        /// Serializes the event, adding the selector of the variant
+        /// to the keys for nested variants.
+        pub fn serialize_event(
+            &self,
+            __keys: &mut Vec<starknet::core::types::FieldElement>,
+            __data: &mut Vec<starknet::core::types::FieldElement>,
+        ) {
+            match self {
+                #(#sers_tokens),*
+            }
         }
     }
 }
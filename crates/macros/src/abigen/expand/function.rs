@@ -9,8 +9,9 @@ use starknet_abigen_parser::{
     abi_types::{AbiType, AbiTypeAny},
     CairoFunction,
 };
+use syn::Ident;
 
-fn get_func_inputs(inputs: &[(String, AbiTypeAny)]) -> Vec<TokenStream2> {
+pub(crate) fn get_func_inputs(inputs: &[(String, AbiTypeAny)]) -> Vec<TokenStream2> {
     let mut out: Vec<TokenStream2> = vec![];
 
     for (name, abi_type) in inputs {
@@ -23,13 +24,91 @@ fn get_func_inputs(inputs: &[(String, AbiTypeAny)]) -> Vec<TokenStream2> {
     out
 }
 
+/// Expands the serialization of the inputs into the `__calldata` vector,
+/// allocated once with the size of all the inputs.
+pub(crate) fn expand_calldata(inputs: &[(String, AbiTypeAny)]) -> TokenStream2 {
+    let mut sizes: Vec<TokenStream2> = vec![];
+    let mut serializations: Vec<TokenStream2> = vec![];
+    for (name, abi_type) in inputs {
+        let name = str_to_ident(name);
+        let ty = str_to_type(&abi_type.to_rust_type_path());
+
+        let ty_punctuated = match abi_type {
+            AbiTypeAny::Tuple(_) => quote!(<#ty>),
+            _ => quote!(#ty),
+        };
+
+        sizes.push(quote!(#ty_punctuated::serialized_size(#name)));
+        serializations.push(quote!(#ty_punctuated::serialize_to(#name, &mut __calldata);));
+    }
+
+    let calldata_size = if sizes.is_empty() {
+        quote!(0)
+    } else {
+        quote!(#(#sizes)+*)
+    };
+
+    quote! {
+        let mut __calldata = Vec::with_capacity(#calldata_size);
+        #(#serializations)*
+    }
+}
+
+/// Expands the payload and message builders of a L1 handler.
+/// The first input of a L1 handler is always the `from_address` of
+/// the L1 contract, which is not part of the message payload.
+pub(crate) fn expand_l1_handler(cf: &CairoFunction) -> TokenStream2 {
+    let func_name = &cf.name;
+    let payload_name = str_to_ident(&format!("{}_payload", cf.name));
+    let message_name = str_to_ident(&format!("{}_message", cf.name));
+
+    let payload_inputs = cf.inputs.get(1..).unwrap_or_default();
+    let inputs = get_func_inputs(payload_inputs);
+    let names: Vec<Ident> = payload_inputs
+        .iter()
+        .map(|(n, _)| str_to_ident(n))
+        .collect();
+    let calldata = expand_calldata(payload_inputs);
+
+    quote! {
+        /// Serializes the arguments of the L1 handler into the payload
+        /// of the message sent from L1.
+        #[allow(clippy::ptr_arg)]
+        pub fn #payload_name(
+            &self,
+            #(#inputs),*
+        ) -> Vec<starknet::core::types::FieldElement> {
+            use starknet_abigen_parser::CairoType;
+
+            #calldata
+            __calldata
+        }
+
+        /// Builds the message sent from the L1 contract `from_address`
+        /// to the L1 handler of this contract.
+        #[allow(clippy::ptr_arg)]
+        pub fn #message_name(
+            &self,
+            from_address: starknet_abigen_parser::cairo_types::EthAddress,
+            #(#inputs),*
+        ) -> starknet_abigen_parser::messaging::L1ToL2Message {
+            starknet_abigen_parser::messaging::L1ToL2Message {
+                from_address,
+                to_address: self.address,
+                selector: starknet::macros::selector!(#func_name),
+                payload: self.#payload_name(#(#names),*),
+            }
+        }
+    }
+}
+
 impl Expandable for CairoFunction {
     fn expand_decl(&self) -> TokenStream2 {
         let func_name = str_to_ident(&self.name);
         let inputs = get_func_inputs(&self.inputs);
 
         let output = match self.state_mutability {
-            StateMutability::View => match &self.output {
+            StateMutability::View => match &self.get_output() {
                 Some(o) => {
                     let oty = str_to_type(&o.to_rust_type());
                     quote!(-> starknet_abigen_parser::cairo_types::Result<#oty>)
@@ -57,21 +136,9 @@ impl Expandable for CairoFunction {
         let decl = self.expand_decl();
         let func_name = &self.name;
 
-        let mut serializations: Vec<TokenStream2> = vec![];
-        for (name, abi_type) in &self.inputs {
-            let name = str_to_ident(name);
-            let ty = str_to_type(&abi_type.to_rust_type_path());
-
-            let ser = match abi_type {
-                AbiTypeAny::Tuple(_) => quote! {
-                    __calldata.extend(<#ty>::serialize(#name));
-                },
-                _ => quote!(__calldata.extend(#ty::serialize(#name));),
-            };
-            serializations.push(ser);
-        }
+        let calldata = expand_calldata(&self.inputs);
 
-        let out_res = match &self.output {
+        let out_res = match &self.get_output() {
             Some(o) => {
                 let out_type_path = str_to_type(&o.to_rust_type_path());
                 match o {
@@ -91,8 +158,7 @@ impl Expandable for CairoFunction {
                     use starknet_abigen_parser::CairoType;
                     use starknet::core::types::{BlockId, BlockTag};
 
-                    let mut __calldata = vec![];
-                    #(#serializations)*
+                    #calldata
 
                     let r = self.provider
                         .call(
@@ -123,8 +189,7 @@ impl Expandable for CairoFunction {
                     ) -> starknet::accounts::Call {
                         use starknet_abigen_parser::CairoType;
 
-                        let mut __calldata = vec![];
-                        #(#serializations)*
+                        #calldata
 
                         starknet::accounts::Call {
                             to: self.address,
@@ -138,8 +203,7 @@ impl Expandable for CairoFunction {
                         use starknet_abigen_parser::CairoType;
                         use starknet::accounts::Account;
 
-                        let mut __calldata = vec![];
-                        #(#serializations)*
+                        #calldata
 
                         let calls = vec![starknet::accounts::Call {
                             to: self.address,
@@ -185,7 +249,7 @@ mod tests {
                 ("v1".to_string(), AbiTypeAny::Basic("core::felt252".into())),
                 ("v2".to_string(), AbiTypeAny::Basic("core::felt252".into())),
             ],
-            output: Some(AbiTypeAny::Basic("core::felt252".into())),
+            outputs: vec![AbiTypeAny::Basic("core::felt252".into())],
         };
         let te1 = cf.expand_decl();
         let tef1: TokenStream2 = quote!(
@@ -195,6 +259,25 @@ mod tests {
         assert_eq!(te1.to_string(), tef1.to_string());
     }
 
+    #[test]
+    fn test_decl_multiple_outputs() {
+        let cf = CairoFunction {
+            name: "my_func".to_string(),
+            state_mutability: StateMutability::View,
+            inputs: vec![("v1".to_string(), AbiTypeAny::Basic("core::felt252".into()))],
+            outputs: vec![
+                AbiTypeAny::Basic("core::felt252".into()),
+                AbiTypeAny::Basic("core::bool".into()),
+            ],
+        };
+        let te1 = cf.expand_decl();
+        let tef1: TokenStream2 = quote!(
+            pub async fn my_func(&self, v1: &starknet::core::types::FieldElement) -> starknet_abigen_parser::cairo_types::Result<(starknet::core::types::FieldElement, bool)>
+        );
+
+        assert_eq!(te1.to_string(), tef1.to_string());
+    }
+
     #[test]
     fn test_impl_basic() {
         let cf = CairoFunction {
@@ -204,7 +287,7 @@ mod tests {
                 ("v1".to_string(), AbiTypeAny::Basic("core::felt252".into())),
                 ("v2".to_string(), AbiTypeAny::Basic("core::felt252".into())),
             ],
-            output: Some(AbiTypeAny::Basic("core::felt252".into())),
+            outputs: vec![AbiTypeAny::Basic("core::felt252".into())],
         };
         let te1 = cf.expand_impl();
 
@@ -219,9 +302,12 @@ mod tests {
                 use starknet_abigen_parser::CairoType;
                 use starknet::core::types::{BlockId, BlockTag};
 
-                let mut __calldata = vec![];
-                __calldata.extend(starknet::core::types::FieldElement::serialize(v1));
-                __calldata.extend(starknet::core::types::FieldElement::serialize(v2));
+                let mut __calldata = Vec::with_capacity(
+                    starknet::core::types::FieldElement::serialized_size(v1)
+                        + starknet::core::types::FieldElement::serialized_size(v2)
+                );
+                starknet::core::types::FieldElement::serialize_to(v1, &mut __calldata);
+                starknet::core::types::FieldElement::serialize_to(v2, &mut __calldata);
 
                 let r = self.provider
                     .call(
@@ -0,0 +1,227 @@
+//! Interfaces expansion.
+//!
+//! Each cairo interface is expanded into two traits:
+//!
+//! * `<Interface>Reader` with the view functions, implemented
+//!   by the contract reader.
+//! * `<Interface>` with the view and external functions, implemented
+//!   by the contract. The view functions are called with the reader
+//!   of the contract.
+//!
+//! The trait functions are calling the functions generated on the
+//! contract and reader, to be generic over any contract implementing
+//! the same interface.
+//!
+//! The SRC5 ID of the interface is also expanded as a constant,
+//! like `IERC20_METADATA_ID` for `IERC20Metadata`.
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use starknet::core::types::contract::StateMutability;
+use starknet::core::types::FieldElement;
+use starknet_abigen_parser::{abi_types::AbiType, CairoFunction};
+use syn::Ident;
+
+use super::function::get_func_inputs;
+use super::utils::{str_to_ident, str_to_type};
+
+/// A cairo interface, with the functions it declares.
+#[derive(Debug, Clone)]
+pub struct CairoInterface {
+    /// Path of the interface in cairo.
+    pub path: String,
+    /// Rust name of the generated trait.
+    pub name: String,
+    pub functions: Vec<CairoFunction>,
+    /// SRC5 ID of the interface.
+    pub id: Option<FieldElement>,
+}
+
+impl CairoInterface {
+    /// Expands the traits declarations of the interface.
+    pub fn expand_decl(&self) -> TokenStream2 {
+        let trait_name = str_to_ident(&self.name);
+        let reader_trait_name = str_to_ident(&format!("{}Reader", self.name));
+
+        let views: Vec<TokenStream2> = self.views().map(expand_view_sig).collect();
+        let externals: Vec<TokenStream2> = self
+            .externals()
+            .flat_map(|f| [expand_getcall_sig(f), expand_external_sig(f)])
+            .collect();
+
+        let id = self.id.map(|id| {
+            let id_name = str_to_ident(&format!("{}_ID", to_upper_snake_case(&self.name)));
+            let doc = format!("SRC5 ID of the `{}` interface.", self.name);
+            let id = format!("{:#x}", id);
+
+            quote! {
+                #[doc = #doc]
+                pub const #id_name: starknet::core::types::FieldElement =
+                    starknet::macros::felt!(#id);
+            }
+        });
+
+        quote! {
+            #id
+
+            pub trait #reader_trait_name {
+                #(
+                    #[allow(clippy::ptr_arg)]
+                    #views;
+                )*
+            }
+
+            pub trait #trait_name {
+                type SignError;
+
+                #(
+                    #[allow(clippy::ptr_arg)]
+                    #views;
+                )*
+                #(
+                    #[allow(clippy::ptr_arg)]
+                    #externals;
+                )*
+            }
+        }
+    }
+
+    /// Expands the traits implementations for the contract and it's reader.
+    pub fn expand_impl(&self, contract_name: &Ident) -> TokenStream2 {
+        let trait_name = str_to_ident(&self.name);
+        let reader_trait_name = str_to_ident(&format!("{}Reader", self.name));
+        let reader = str_to_ident(&format!("{}Reader", contract_name));
+
+        let mut reader_views = vec![];
+        let mut contract_views = vec![];
+        for f in self.views() {
+            let sig = expand_view_sig(f);
+            let func_name = str_to_ident(&f.name);
+            let names = input_names(f);
+
+            reader_views.push(quote! {
+                #[allow(clippy::ptr_arg)]
+                #sig {
+                    async move { #reader::#func_name(self, #(#names),*).await }
+                }
+            });
+
+            contract_views.push(quote! {
+                #[allow(clippy::ptr_arg)]
+                #sig {
+                    async move { self.reader().#func_name(#(#names),*).await }
+                }
+            });
+        }
+
+        let mut externals = vec![];
+        for f in self.externals() {
+            let getcall_sig = expand_getcall_sig(f);
+            let external_sig = expand_external_sig(f);
+            let func_name = str_to_ident(&f.name);
+            let getcall_name = str_to_ident(&format!("{}_getcall", f.name));
+            let names = input_names(f);
+
+            externals.push(quote! {
+                #[allow(clippy::ptr_arg)]
+                #getcall_sig {
+                    #contract_name::#getcall_name(self, #(#names),*)
+                }
+
+                #[allow(clippy::ptr_arg)]
+                #external_sig {
+                    async move { #contract_name::#func_name(self, #(#names),*).await }
+                }
+            });
+        }
+
+        quote! {
+            impl<'a, P: starknet::providers::Provider + Sync> #reader_trait_name for #reader<'a, P> {
+                #(#reader_views)*
+            }
+
+            impl<A: starknet::accounts::ConnectedAccount + Sync> #trait_name for #contract_name<A> {
+                type SignError = A::SignError;
+
+                #(#contract_views)*
+                #(#externals)*
+            }
+        }
+    }
+
+    fn views(&self) -> impl Iterator<Item = &CairoFunction> {
+        self.functions
+            .iter()
+            .filter(|f| matches!(f.state_mutability, StateMutability::View))
+    }
+
+    fn externals(&self) -> impl Iterator<Item = &CairoFunction> {
+        self.functions
+            .iter()
+            .filter(|f| matches!(f.state_mutability, StateMutability::External))
+    }
+}
+
+/// Converts a trait name into upper snake case, keeping the
+/// acronyms together: `IERC20Metadata` becomes `IERC20_METADATA`.
+fn to_upper_snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    let mut prev: Option<char> = None;
+
+    for c in name.chars() {
+        if c.is_uppercase() && prev.is_some_and(|p| p.is_lowercase() || p.is_ascii_digit()) {
+            snake.push('_');
+        }
+
+        snake.push(c.to_ascii_uppercase());
+        prev = Some(c);
+    }
+
+    snake
+}
+
+fn input_names(f: &CairoFunction) -> Vec<Ident> {
+    f.inputs.iter().map(|(n, _)| str_to_ident(n)).collect()
+}
+
+/// Signature of a view function in the traits.
+fn expand_view_sig(f: &CairoFunction) -> TokenStream2 {
+    let func_name = str_to_ident(&f.name);
+    let inputs = get_func_inputs(&f.inputs);
+
+    let output = match f.get_output() {
+        Some(o) => {
+            let oty = str_to_type(&o.to_rust_type());
+            quote!(starknet_abigen_parser::cairo_types::Result<#oty>)
+        }
+        None => quote!(starknet_abigen_parser::cairo_types::Result<()>),
+    };
+
+    quote! {
+        fn #func_name(&self, #(#inputs),*) -> impl std::future::Future<Output = #output> + Send
+    }
+}
+
+/// Signature of the `Call` getter of an external function in the trait.
+fn expand_getcall_sig(f: &CairoFunction) -> TokenStream2 {
+    let func_name = str_to_ident(&format!("{}_getcall", f.name));
+    let inputs = get_func_inputs(&f.inputs);
+
+    quote! {
+        fn #func_name(&self, #(#inputs),*) -> starknet::accounts::Call
+    }
+}
+
+/// Signature of an external function in the trait.
+fn expand_external_sig(f: &CairoFunction) -> TokenStream2 {
+    let func_name = str_to_ident(&f.name);
+    let inputs = get_func_inputs(&f.inputs);
+
+    quote! {
+        fn #func_name(&self, #(#inputs),*) -> impl std::future::Future<
+            Output = Result<
+                starknet::core::types::InvokeTransactionResult,
+                starknet::accounts::AccountError<Self::SignError>,
+            >,
+        > + Send
+    }
+}
@@ -3,6 +3,7 @@ pub(crate) mod r#enum;
 pub(crate) mod event;
 pub(crate) mod function;
 pub(crate) mod generic;
+pub(crate) mod interface;
 pub(crate) mod r#struct;
 pub(crate) mod utils;
 
@@ -46,7 +46,8 @@ impl Expandable for CairoStruct {
     }
 
     fn expand_impl(&self) -> TokenStream2 {
-        let struct_name = str_to_ident(&self.get_name());
+        let name_str = &self.get_name();
+        let struct_name = str_to_ident(name_str);
 
         let mut sizes: Vec<TokenStream2> = vec![];
         let mut sers: Vec<TokenStream2> = vec![];
@@ -55,6 +56,7 @@ impl Expandable for CairoStruct {
 
         let mut is_first = true;
         for (name, abi_type) in &self.members {
+            let segment = format!(".{}", name);
             let name = str_to_ident(name);
             names.push(quote!(#name));
 
@@ -74,14 +76,21 @@ impl Expandable for CairoStruct {
                 sizes.push(quote!(+ #ty_punctuated::serialized_size(&__rust.#name)));
             }
 
-            sers.push(quote!(__out.extend(#ty_punctuated::serialize(&__rust.#name));));
+            sers.push(quote!(#ty_punctuated::serialize_to(&__rust.#name, __out);));
 
             desers.push(quote! {
-                let #name = #ty_punctuated::deserialize(__felts, __offset)?;
-                __offset += #ty_punctuated::serialized_size(&#name);
+                let (#name, __size) = #ty_punctuated::deserialize_with_size(__felts, __offset)
+                    .map_err(|e| e.with_context(#name_str, #segment, __offset))?;
+                __offset += __size;
             });
         }
 
+        // Structs without members, like untyped events without inputs,
+        // are serialized into no felts.
+        if sizes.is_empty() {
+            sizes.push(quote!(0));
+        }
+
         let gentys: Vec<Ident> = self.get_gentys().iter().map(|g| str_to_ident(g)).collect();
 
         let impl_line = if self.is_generic() {
@@ -111,17 +120,22 @@ impl Expandable for CairoStruct {
                 }
 
                 fn serialize(__rust: &Self::RustType) -> Vec<starknet::core::types::FieldElement> {
-                    let mut __out: Vec<starknet::core::types::FieldElement> = vec![];
-                    #(#sers)*
+                    let mut __out = Vec::with_capacity(Self::serialized_size(__rust));
+                    Self::serialize_to(__rust, &mut __out);
                     __out
                 }
 
-                fn deserialize(__felts: &[starknet::core::types::FieldElement], __offset: usize) -> starknet_abigen_parser::cairo_types::Result<Self::RustType> {
+                fn serialize_to(__rust: &Self::RustType, __out: &mut Vec<starknet::core::types::FieldElement>) {
+                    #(#sers)*
+                }
+
+                fn deserialize_with_size(__felts: &[starknet::core::types::FieldElement], __offset: usize) -> starknet_abigen_parser::cairo_types::Result<(Self::RustType, usize)> {
+                    let __start = __offset;
                     let mut __offset = __offset;
                     #(#desers)*
-                    Ok(#struct_name {
+                    Ok((#struct_name {
                         #(#names),*
-                    })
+                    }, __offset - __start))
                 }
             }
         }
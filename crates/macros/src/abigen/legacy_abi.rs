@@ -0,0 +1,326 @@
+//! Conversion of a legacy (Cairo 0) ABI into a Sierra ABI.
+//!
+//! Legacy ABIs are converted upfront, to then generate the bindings
+//! exactly as any Sierra ABI:
+//!
+//! * `felt` is converted into `core::felt252`, and the `Uint256` struct
+//!   into `core::integer::u256`, which share the same serialization.
+//! * Pointers are always preceded by their length argument, named
+//!   `<name>_len`. Both are folded into a single `Array` argument, which
+//!   serializes the length followed by the elements.
+//! * Functions with several outputs return a generated struct,
+//!   named after the function, with a member for each output.
+//! * Events are struct events, which are grouped into the `Event` enum
+//!   generated for contracts without typed events.
+use serde_json::Value;
+use starknet::core::types::contract::{
+    AbiConstructor, AbiEntry, AbiEvent, AbiEventStruct, AbiFunction, AbiNamedMember, AbiOutput,
+    AbiStruct, EventField, EventFieldKind, StateMutability, TypedAbiEvent,
+};
+use starknet::core::types::{
+    FunctionStateMutability, LegacyContractAbiEntry, LegacyFunctionAbiType, LegacyTypedParameter,
+};
+
+use super::to_camel_case;
+
+const LEGACY_FELT: &str = "felt";
+const LEGACY_U256: &str = "Uint256";
+const CAIRO_FELT: &str = "core::felt252";
+
+/// Checks if the ABI is a legacy ABI. Legacy structs always declare their
+/// `size`, functions have no `state_mutability` and events declare
+/// their `keys` and `data`, which are never found in a Sierra ABI.
+pub(crate) fn is_legacy_abi(abi: &Value) -> bool {
+    let Some(entries) = abi.as_array() else {
+        return false;
+    };
+
+    entries
+        .iter()
+        .any(|entry| match entry.get("type").and_then(Value::as_str) {
+            Some("struct") => entry.get("size").is_some(),
+            Some("function") => entry.get("state_mutability").is_none(),
+            Some("event") => entry.get("data").is_some(),
+            _ => false,
+        })
+}
+
+/// Converts the legacy ABI entries into Sierra ABI entries.
+pub(crate) fn from_legacy_abi(entries: &[LegacyContractAbiEntry]) -> Result<Vec<AbiEntry>, String> {
+    let mut abi = vec![];
+
+    for entry in entries {
+        match entry {
+            LegacyContractAbiEntry::Function(f) => {
+                let inputs = fold_params(&f.inputs)?;
+
+                match f.r#type {
+                    LegacyFunctionAbiType::Constructor => {
+                        abi.push(AbiEntry::Constructor(AbiConstructor {
+                            name: f.name.clone(),
+                            inputs,
+                        }));
+                    }
+                    LegacyFunctionAbiType::Function | LegacyFunctionAbiType::L1Handler => {
+                        let outputs = fold_params(&f.outputs)?;
+
+                        let outputs = if outputs.len() > 1 {
+                            let name = format!("{}Output", to_camel_case(&f.name));
+                            abi.push(AbiEntry::Struct(AbiStruct {
+                                name: name.clone(),
+                                members: outputs,
+                            }));
+
+                            vec![AbiOutput { r#type: name }]
+                        } else {
+                            outputs
+                                .into_iter()
+                                .map(|o| AbiOutput { r#type: o.r#type })
+                                .collect()
+                        };
+
+                        let state_mutability = match f.state_mutability {
+                            Some(FunctionStateMutability::View) => StateMutability::View,
+                            None => StateMutability::External,
+                        };
+
+                        let func = AbiFunction {
+                            name: f.name.clone(),
+                            inputs,
+                            outputs,
+                            state_mutability,
+                        };
+
+                        abi.push(match f.r#type {
+                            LegacyFunctionAbiType::L1Handler => AbiEntry::L1Handler(func),
+                            _ => AbiEntry::Function(func),
+                        });
+                    }
+                }
+            }
+            LegacyContractAbiEntry::Event(e) => {
+                let with_kind = |members: Vec<AbiNamedMember>, kind: EventFieldKind| {
+                    members.into_iter().map(move |m| EventField {
+                        name: m.name,
+                        r#type: m.r#type,
+                        kind: kind.clone(),
+                    })
+                };
+
+                let members = with_kind(fold_params(&e.keys)?, EventFieldKind::Key)
+                    .chain(with_kind(fold_params(&e.data)?, EventFieldKind::Data))
+                    .collect();
+
+                abi.push(AbiEntry::Event(AbiEvent::Typed(TypedAbiEvent::Struct(
+                    AbiEventStruct {
+                        name: e.name.clone(),
+                        members,
+                    },
+                ))));
+            }
+            LegacyContractAbiEntry::Struct(s) => {
+                // Already mapped to the `u256` builtin.
+                if s.name == LEGACY_U256 {
+                    continue;
+                }
+
+                let members = s
+                    .members
+                    .iter()
+                    .map(|m| {
+                        Ok(AbiNamedMember {
+                            name: m.name.clone(),
+                            r#type: legacy_type_to_cairo(&m.r#type)?,
+                        })
+                    })
+                    .collect::<Result<Vec<_>, String>>()?;
+
+                abi.push(AbiEntry::Struct(AbiStruct {
+                    name: s.name.clone(),
+                    members,
+                }));
+            }
+        }
+    }
+
+    Ok(abi)
+}
+
+/// Converts the parameters types, folding each pointer with it's
+/// preceding length parameter into an array.
+fn fold_params(params: &[LegacyTypedParameter]) -> Result<Vec<AbiNamedMember>, String> {
+    let mut members: Vec<AbiNamedMember> = vec![];
+
+    for p in params {
+        let Some(elem) = p.r#type.strip_suffix('*') else {
+            members.push(AbiNamedMember {
+                name: p.name.clone(),
+                r#type: legacy_type_to_cairo(&p.r#type)?,
+            });
+            continue;
+        };
+
+        let len_name = format!("{}_len", p.name);
+        match members.last() {
+            Some(m) if m.name == len_name && m.r#type == CAIRO_FELT => {
+                members.pop();
+            }
+            _ => {
+                return Err(format!(
+                    "pointer `{}` must be preceded by `{}: felt`",
+                    p.name, len_name
+                ))
+            }
+        }
+
+        members.push(AbiNamedMember {
+            name: p.name.clone(),
+            r#type: format!("core::array::Array::<{}>", legacy_type_to_cairo(elem)?),
+        });
+    }
+
+    Ok(members)
+}
+
+/// Converts a legacy type into a Sierra type.
+fn legacy_type_to_cairo(legacy_type: &str) -> Result<String, String> {
+    let t = legacy_type.trim();
+
+    if let Some(inner) = t.strip_prefix('(').and_then(|t| t.strip_suffix(')')) {
+        let items = split_tuple_items(inner)
+            .iter()
+            .map(|item| {
+                // Tuple items may be named, like `(x: felt, y: felt)`.
+                let item_type = match item.split_once(':') {
+                    Some((_, item_type)) if !item.trim_start().starts_with('(') => item_type,
+                    _ => item,
+                };
+                legacy_type_to_cairo(item_type)
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        return Ok(format!("({})", items.join(", ")));
+    }
+
+    if t.ends_with('*') {
+        return Err(format!(
+            "pointer type `{}` is only supported as an argument",
+            t
+        ));
+    }
+
+    Ok(match t {
+        LEGACY_FELT => CAIRO_FELT.to_string(),
+        LEGACY_U256 => "core::integer::u256".to_string(),
+        _ => t.to_string(),
+    })
+}
+
+/// Splits the items of a tuple on the top level commas.
+fn split_tuple_items(inner: &str) -> Vec<&str> {
+    let mut items = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+
+    for (i, c) in inner.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                items.push(&inner[start..i]);
+                start = i + 1;
+            }
+            _ => (),
+        }
+    }
+
+    if !inner[start..].trim().is_empty() {
+        items.push(&inner[start..]);
+    }
+
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn param(name: &str, r#type: &str) -> LegacyTypedParameter {
+        LegacyTypedParameter {
+            name: name.to_string(),
+            r#type: r#type.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_is_legacy_abi() {
+        let legacy: Value =
+            serde_json::from_str(r#"[{"type":"function","name":"f","inputs":[],"outputs":[]}]"#)
+                .unwrap();
+        assert!(is_legacy_abi(&legacy));
+
+        let sierra: Value = serde_json::from_str(
+            r#"[{"type":"function","name":"f","inputs":[],"outputs":[],"state_mutability":"view"}]"#,
+        )
+        .unwrap();
+        assert!(!is_legacy_abi(&sierra));
+    }
+
+    #[test]
+    fn test_legacy_type_to_cairo() {
+        assert_eq!(legacy_type_to_cairo("felt").unwrap(), "core::felt252");
+        assert_eq!(
+            legacy_type_to_cairo("Uint256").unwrap(),
+            "core::integer::u256"
+        );
+        assert_eq!(legacy_type_to_cairo("Point").unwrap(), "Point");
+        assert_eq!(
+            legacy_type_to_cairo("(x: felt, y: (felt, Uint256), (a: felt, b: felt))").unwrap(),
+            "(core::felt252, (core::felt252, core::integer::u256), (core::felt252, core::felt252))"
+        );
+        assert!(legacy_type_to_cairo("felt*").is_err());
+    }
+
+    #[test]
+    fn test_fold_params() {
+        let members = fold_params(&[
+            param("a", "felt"),
+            param("arr_len", "felt"),
+            param("arr", "Point*"),
+        ])
+        .unwrap();
+
+        assert_eq!(members.len(), 2);
+        assert_eq!(members[0].name, "a");
+        assert_eq!(members[1].name, "arr");
+        assert_eq!(members[1].r#type, "core::array::Array::<Point>");
+
+        assert!(fold_params(&[param("arr", "felt*")]).is_err());
+        assert!(fold_params(&[param("len", "felt"), param("arr", "felt*")]).is_err());
+    }
+
+    #[test]
+    fn test_from_legacy_abi_outputs() {
+        let abi: Vec<LegacyContractAbiEntry> = serde_json::from_str(
+            r#"[{"type":"function","name":"get_balances","inputs":[],
+                "outputs":[{"name":"a","type":"felt"},{"name":"b","type":"Uint256"}],
+                "stateMutability":"view"}]"#,
+        )
+        .unwrap();
+
+        let abi = from_legacy_abi(&abi).unwrap();
+        assert_eq!(abi.len(), 2);
+
+        let AbiEntry::Struct(s) = &abi[0] else {
+            panic!("Expected output struct");
+        };
+        assert_eq!(s.name, "GetBalancesOutput");
+        assert_eq!(s.members[1].r#type, "core::integer::u256");
+
+        let AbiEntry::Function(f) = &abi[1] else {
+            panic!("Expected function");
+        };
+        assert_eq!(f.outputs[0].r#type, "GetBalancesOutput");
+        assert!(matches!(f.state_mutability, StateMutability::View));
+    }
+}
@@ -16,45 +16,54 @@ use syn::parse_macro_input;
 
 use std::collections::HashMap;
 
-use starknet::core::types::contract::{AbiEntry, StateMutability};
+use starknet::core::types::contract::{AbiEntry, AbiEvent, StateMutability, TypedAbiEvent};
+use starknet_abigen_parser::abi_types::{AbiType, AbiTypeAny};
 use starknet_abigen_parser::cairo_types::{CAIRO_BASIC_ENUMS, CAIRO_BASIC_STRUCTS};
-use starknet_abigen_parser::{CairoEnum, CairoEvent, CairoFunction, CairoStruct};
+use starknet_abigen_parser::src5::interface_id;
+use starknet_abigen_parser::{CairoEnum, CairoEvent, CairoEventInner, CairoFunction, CairoStruct};
 
 mod expand;
 use expand::contract::CairoContract;
+use expand::function::expand_l1_handler;
+use expand::interface::CairoInterface;
 use expand::{Expandable, ExpandableEvent};
 
 mod contract_abi;
 use contract_abi::ContractAbi;
 
+mod legacy_abi;
+
 use crate::abigen::expand::utils;
 
 pub fn abigen_internal(input: TokenStream) -> TokenStream {
     let contract_abi = parse_macro_input!(input as ContractAbi);
     let contract_name = contract_abi.name;
     let abi = contract_abi.abi;
+    let flat_event_fields = contract_abi.flat_event_fields;
 
     let mut tokens: Vec<TokenStream2> = vec![];
 
     tokens.push(CairoContract::expand(contract_name.clone()));
 
-    let mut structs: HashMap<String, CairoStruct> = HashMap::new();
-    let mut enums: HashMap<String, CairoEnum> = HashMap::new();
-    let mut views = vec![];
-    let mut externals = vec![];
-    let mut events = vec![];
+    let aliases = get_type_aliases(&abi);
 
+    let mut parsed = ParsedAbi::default();
     for entry in &abi {
-        parse_entry(
-            entry,
-            &mut structs,
-            &mut enums,
-            &mut externals,
-            &mut views,
-            &mut events,
-        );
+        parse_entry(entry, &abi, &aliases, &mut parsed);
     }
 
+    let ParsedAbi {
+        structs,
+        enums,
+        externals,
+        views,
+        l1_handlers,
+        mut events,
+        constructor,
+        interfaces,
+        impls,
+    } = parsed;
+
     for (_, cs) in structs {
         tokens.push(cs.expand_decl());
         tokens.push(cs.expand_impl());
@@ -65,11 +74,46 @@ pub fn abigen_internal(input: TokenStream) -> TokenStream {
         tokens.push(ce.expand_impl());
     }
 
+    for ev in &mut events {
+        if let Some(names) = flat_event_fields.get(&ev.abi.get_cairo_type_full()) {
+            ev.set_flat_fields(names);
+        }
+    }
+
+    // Untyped events are not grouped into an `Event` enum in the ABI.
+    if get_contract_event(&events).is_none() {
+        if let Some(ev) = get_untyped_event_enum(&events) {
+            events.push(ev);
+        }
+    }
+
     for ev in &events {
         tokens.push(ev.expand_decl());
         tokens.push(ev.expand_impl(&events));
     }
 
+    if let Some(ev) = get_contract_event(&events) {
+        let event = utils::str_to_ident(&ev.get_name());
+        tokens.push(CairoContract::expand_events(contract_name.clone(), event));
+    }
+
+    // Contracts without constructor are deployed without arguments.
+    let constructor_inputs = constructor.map(|c| c.inputs).unwrap_or_default();
+    tokens.push(CairoContract::expand_constructor(
+        contract_name.clone(),
+        &constructor_inputs,
+    ));
+
+    // Older ABIs don't declare the impls, all the interfaces
+    // are then considered implemented by the contract.
+    for interface in &interfaces {
+        tokens.push(interface.expand_decl());
+
+        if impls.is_empty() || impls.contains(&interface.path) {
+            tokens.push(interface.expand_impl(&contract_name));
+        }
+    }
+
     let reader = utils::str_to_ident(format!("{}Reader", contract_name).as_str());
     tokens.push(quote! {
         impl<A: starknet::accounts::ConnectedAccount + Sync> #contract_name<A> {
@@ -78,6 +122,7 @@ pub fn abigen_internal(input: TokenStream) -> TokenStream {
 
         impl<'a, P: starknet::providers::Provider + Sync> #reader<'a, P> {
             #(#views)*
+            #(#l1_handlers)*
         }
     });
 
@@ -88,61 +133,372 @@ pub fn abigen_internal(input: TokenStream) -> TokenStream {
     expanded.into()
 }
 
+/// Types and functions parsed from the ABI entries.
+#[derive(Default)]
+struct ParsedAbi {
+    structs: HashMap<String, CairoStruct>,
+    enums: HashMap<String, CairoEnum>,
+    externals: Vec<TokenStream2>,
+    views: Vec<TokenStream2>,
+    l1_handlers: Vec<TokenStream2>,
+    events: Vec<CairoEvent>,
+    constructor: Option<CairoFunction>,
+    interfaces: Vec<CairoInterface>,
+    /// Paths of the interfaces implemented by the contract.
+    impls: Vec<String>,
+}
+
 fn parse_entry(
     entry: &AbiEntry,
-    structs: &mut HashMap<String, CairoStruct>,
-    enums: &mut HashMap<String, CairoEnum>,
-    externals: &mut Vec<TokenStream2>,
-    views: &mut Vec<TokenStream2>,
-    events: &mut Vec<CairoEvent>,
+    abi: &[AbiEntry],
+    aliases: &HashMap<String, String>,
+    parsed: &mut ParsedAbi,
 ) {
     match entry {
         AbiEntry::Struct(s) => {
-            let cs = CairoStruct::new(&s.name, &s.members);
+            let mut cs = CairoStruct::new(&s.name, &s.members);
+            cs.apply_aliases(aliases);
 
             if CAIRO_BASIC_STRUCTS.contains(&cs.get_name().as_str()) {
                 return;
             }
 
-            if let Some(ref mut existing_cs) = structs.get_mut(&cs.get_name()) {
+            if let Some(ref mut existing_cs) = parsed.structs.get_mut(&cs.get_name()) {
                 cs.compare_generic_types(existing_cs);
             } else {
-                structs.insert(cs.get_name(), cs.clone());
+                parsed.structs.insert(cs.get_name(), cs.clone());
             }
         }
         AbiEntry::Enum(e) => {
-            let ce = CairoEnum::new(&e.name, &e.variants);
+            let mut ce = CairoEnum::new(&e.name, &e.variants);
+            ce.apply_aliases(aliases);
 
             if CAIRO_BASIC_ENUMS.contains(&ce.get_name().as_str()) {
                 return;
             }
 
-            if let Some(ref mut existing_ce) = enums.get_mut(&ce.get_name()) {
+            if let Some(ref mut existing_ce) = parsed.enums.get_mut(&ce.get_name()) {
                 ce.compare_generic_types(existing_ce);
             } else {
-                enums.insert(ce.get_name(), ce.clone());
+                parsed.enums.insert(ce.get_name(), ce.clone());
             }
         }
         AbiEntry::Function(f) => {
             // Functions cannot be generic when they are entry point.
             // From this statement, we can safely assume that any function name is
             // unique.
-            let cf = CairoFunction::new(&f.name, f.state_mutability.clone(), &f.inputs, &f.outputs);
+            let mut cf =
+                CairoFunction::new(&f.name, f.state_mutability.clone(), &f.inputs, &f.outputs);
+            cf.apply_aliases(aliases);
+
             match f.state_mutability {
-                StateMutability::View => views.push(cf.expand_impl()),
-                StateMutability::External => externals.push(cf.expand_impl()),
+                StateMutability::View => parsed.views.push(cf.expand_impl()),
+                StateMutability::External => parsed.externals.push(cf.expand_impl()),
             }
         }
+        AbiEntry::L1Handler(f) => {
+            let mut cf =
+                CairoFunction::new(&f.name, f.state_mutability.clone(), &f.inputs, &f.outputs);
+            cf.apply_aliases(aliases);
+            parsed.l1_handlers.push(expand_l1_handler(&cf));
+        }
+        AbiEntry::Constructor(c) => {
+            let mut cf = CairoFunction::new(&c.name, StateMutability::External, &c.inputs, &[]);
+            cf.apply_aliases(aliases);
+            parsed.constructor = Some(cf);
+        }
         AbiEntry::Event(ev) => {
-            if let Some(cev) = CairoEvent::new(ev) {
-                events.push(cev);
+            if let Some(mut cev) = CairoEvent::new(ev) {
+                cev.apply_aliases(aliases);
+                parsed.events.push(cev);
             }
         }
         AbiEntry::Interface(interface) => {
+            let mut functions = vec![];
+
             for entry in &interface.items {
-                parse_entry(entry, structs, enums, externals, views, events);
+                parse_entry(entry, abi, aliases, parsed);
+
+                if let AbiEntry::Function(f) = entry {
+                    let mut cf = CairoFunction::new(
+                        &f.name,
+                        f.state_mutability.clone(),
+                        &f.inputs,
+                        &f.outputs,
+                    );
+                    cf.apply_aliases(aliases);
+                    functions.push(cf);
+                }
+            }
+
+            let name = match aliases.get(&interface.name) {
+                Some(alias) => alias.clone(),
+                None => AbiTypeAny::from_string(&interface.name).get_cairo_type_name(),
+            };
+
+            parsed.interfaces.push(CairoInterface {
+                path: interface.name.clone(),
+                name,
+                functions,
+                // Interfaces with types not found in the ABI have no ID.
+                id: interface_id(interface, abi).ok(),
+            });
+        }
+        AbiEntry::Impl(i) => parsed.impls.push(i.interface_name.clone()),
+    }
+}
+
+/// Checks if the event is a variant of any of the enum events.
+fn is_event_variant(events: &[CairoEvent], ev: &CairoEvent) -> bool {
+    events.iter().any(|other| match &other.inner {
+        CairoEventInner::Enum(e) => e
+            .variants
+            .iter()
+            .any(|(_, abi)| abi.get_cairo_type_full() == ev.abi.get_cairo_type_full()),
+        CairoEventInner::Struct(_) => false,
+    })
+}
+
+/// Gets the `Event` enum of the contract, which is the only `Event` enum
+/// not being a variant of an other event, as components `Event` enums are.
+fn get_contract_event(events: &[CairoEvent]) -> Option<&CairoEvent> {
+    events.iter().find(|ev| {
+        ev.get_cairo_name() == "Event"
+            && matches!(ev.inner, CairoEventInner::Enum(_))
+            && !is_event_variant(events, ev)
+    })
+}
+
+/// Builds the `Event` enum of a contract with untyped events, with a variant
+/// for each struct event not being already a variant of an other event.
+fn get_untyped_event_enum(events: &[CairoEvent]) -> Option<CairoEvent> {
+    let variants: Vec<&CairoEvent> = events
+        .iter()
+        .filter(|ev| matches!(ev.inner, CairoEventInner::Struct(_)))
+        .filter(|ev| !is_event_variant(events, ev))
+        .collect();
+
+    if variants.is_empty() {
+        return None;
+    }
+
+    Some(CairoEvent::new_event_enum("Event", &variants))
+}
+
+/// Computes a rust name for each struct, enum or event of the ABI
+/// that shares it's name with an other type declared in a different
+/// cairo module. Those types would otherwise collide once expanded.
+///
+/// The `Event` enum of the contract always keeps it's name, only the
+/// `Event` enums of the components are aliased.
+///
+/// Returns the aliases indexed by cairo type path.
+fn get_type_aliases(abi: &[AbiEntry]) -> HashMap<String, String> {
+    let mut paths = vec![];
+    collect_type_paths(abi, &mut paths);
+
+    let contract_event = get_contract_event_path(abi);
+    paths.retain(|p| Some(p) != contract_event.as_ref());
+
+    let mut paths_by_name: HashMap<String, Vec<String>> = HashMap::new();
+    for path in paths {
+        let name = path.split("::").last().unwrap_or(&path).to_string();
+        let same_name_paths = paths_by_name.entry(name).or_default();
+
+        if !same_name_paths.contains(&path) {
+            same_name_paths.push(path);
+        }
+    }
+
+    let mut aliases = HashMap::new();
+    for (name, same_name_paths) in paths_by_name {
+        // The components `Event` enums collide with the contract `Event`.
+        let collides_contract_event = name == "Event" && contract_event.is_some();
+
+        if same_name_paths.len() > 1 || collides_contract_event {
+            aliases.extend(disambiguate_paths(&same_name_paths));
+        }
+    }
+
+    aliases
+}
+
+/// Gets the path of the `Event` enum of the contract, which is the only
+/// `Event` enum not being a variant of an other event.
+fn get_contract_event_path(abi: &[AbiEntry]) -> Option<String> {
+    let enums: Vec<_> = abi
+        .iter()
+        .filter_map(|entry| match entry {
+            AbiEntry::Event(AbiEvent::Typed(TypedAbiEvent::Enum(e))) => Some(e),
+            _ => None,
+        })
+        .collect();
+
+    enums
+        .iter()
+        .find(|e| {
+            e.name.split("::").last() == Some("Event")
+                && !enums
+                    .iter()
+                    .any(|other| other.variants.iter().any(|v| v.r#type == e.name))
+        })
+        .map(|e| e.name.clone())
+}
+
+/// Collects the cairo type path (without generic arguments) of
+/// every type declared in the ABI.
+fn collect_type_paths(abi: &[AbiEntry], paths: &mut Vec<String>) {
+    for entry in abi {
+        let name = match entry {
+            AbiEntry::Struct(s) => &s.name,
+            AbiEntry::Enum(e) => &e.name,
+            AbiEntry::Event(AbiEvent::Typed(TypedAbiEvent::Struct(s))) => &s.name,
+            AbiEntry::Event(AbiEvent::Typed(TypedAbiEvent::Enum(e))) => &e.name,
+            AbiEntry::Event(AbiEvent::Untyped(e)) => &e.name,
+            // Interfaces are expanded into traits, which may collide too.
+            AbiEntry::Interface(interface) => {
+                collect_type_paths(&interface.items, paths);
+                &interface.name
             }
+            _ => continue,
+        };
+
+        let abi_type = AbiTypeAny::from_string(name);
+        let type_name = abi_type.get_cairo_type_name();
+
+        if CAIRO_BASIC_STRUCTS.contains(&type_name.as_str())
+            || CAIRO_BASIC_ENUMS.contains(&type_name.as_str())
+        {
+            continue;
         }
-        _ => (),
+
+        paths.push(abi_type.get_cairo_type_path());
+    }
+}
+
+/// Builds a unique rust name for each of the given paths, all ending with
+/// the same type name. The shortest suffix of modules making the names unique
+/// is converted to camel case and prepended to the type name.
+///
+/// `contract::a::Position` and `contract::b::Position` are then
+/// respectively named `APosition` and `BPosition`.
+fn disambiguate_paths(paths: &[String]) -> Vec<(String, String)> {
+    let segments: Vec<Vec<&str>> = paths.iter().map(|p| p.split("::").collect()).collect();
+    let max_len = segments.iter().map(|s| s.len()).max().unwrap_or(1);
+
+    for n in 2..=max_len {
+        let names: Vec<String> = segments
+            .iter()
+            .map(|s| {
+                let suffix = &s[s.len().saturating_sub(n)..];
+                let (name, modules) = suffix.split_last().expect("Path can't be empty");
+
+                let mut alias: String = modules.iter().map(|m| to_camel_case(m)).collect();
+                alias.push_str(name);
+                alias
+            })
+            .collect();
+
+        let is_unique = names
+            .iter()
+            .enumerate()
+            .all(|(i, n)| !names[..i].contains(n));
+
+        if is_unique {
+            return paths.iter().cloned().zip(names).collect();
+        }
+    }
+
+    // Different paths may still be converted to the same camel case
+    // (like `a_b::T` and `a::b::T`), in which case the position is used.
+    paths
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            let name = p.split("::").last().unwrap_or(p);
+            (p.clone(), format!("{}{}", name, i))
+        })
+        .collect()
+}
+
+/// Converts a snake case cairo module (or function) name into camel case.
+pub(crate) fn to_camel_case(module: &str) -> String {
+    module
+        .split('_')
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(c) => c.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disambiguate_paths() {
+        let paths = vec![
+            "contract::a::Position".to_string(),
+            "contract::b_c::Position".to_string(),
+        ];
+
+        assert_eq!(
+            disambiguate_paths(&paths),
+            vec![
+                ("contract::a::Position".to_string(), "APosition".to_string()),
+                (
+                    "contract::b_c::Position".to_string(),
+                    "BCPosition".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_disambiguate_paths_different_depth() {
+        let paths = vec![
+            "contract::Position".to_string(),
+            "contract::a::Position".to_string(),
+            "other::a::Position".to_string(),
+        ];
+
+        assert_eq!(
+            disambiguate_paths(&paths),
+            vec![
+                (
+                    "contract::Position".to_string(),
+                    "ContractPosition".to_string()
+                ),
+                (
+                    "contract::a::Position".to_string(),
+                    "ContractAPosition".to_string()
+                ),
+                (
+                    "other::a::Position".to_string(),
+                    "OtherAPosition".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_type_aliases_keep_contract_event() {
+        let abi: Vec<AbiEntry> = serde_json::from_str(
+            r#"[
+            {"type":"event","name":"c::Event","kind":"enum","variants":[
+                {"name":"OwnableEvent","type":"o::ownable::Event","kind":"nested"}
+            ]},
+            {"type":"event","name":"o::ownable::Event","kind":"enum","variants":[]}
+        ]"#,
+        )
+        .unwrap();
+
+        let aliases = get_type_aliases(&abi);
+        assert_eq!(aliases.len(), 1);
+        assert_eq!(aliases["o::ownable::Event"], "OwnableEvent");
     }
 }
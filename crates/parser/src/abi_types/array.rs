@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use super::{AbiType, AbiTypeAny, GENTY_FROZEN};
 
 #[derive(Debug, PartialEq, Clone)]
@@ -82,6 +84,10 @@ impl AbiType for AbiArray {
             format!("Vec::<{}>", &self.inner.to_rust_type())
         }
     }
+
+    fn apply_aliases(&mut self, aliases: &HashMap<String, String>) {
+        self.inner.apply_aliases(aliases);
+    }
 }
 
 #[cfg(test)]
@@ -3,6 +3,8 @@
 //!
 //! To support recursion, the basic type stored the generic type
 //! that is assigned to it, if it belongs to a generic struct/enum.
+use std::collections::HashMap;
+
 use super::{AbiType, AbiTypeAny, GENTY_FROZEN};
 use crate::cairo_types::CAIRO_TYPES_PATH;
 
@@ -10,6 +12,7 @@ use crate::cairo_types::CAIRO_TYPES_PATH;
 pub struct AbiBasic {
     cairo_type: String,
     genty: String,
+    alias: Option<String>,
 }
 
 impl AbiBasic {
@@ -18,9 +21,18 @@ impl AbiBasic {
         AbiBasic {
             cairo_type: cairo_type.to_string(),
             genty: String::new(),
+            alias: None,
         }
     }
 
+    /// Returns the name used for the type in rust. It's the cairo type
+    /// name, unless an alias was applied.
+    pub fn get_rust_type_name(&self) -> String {
+        self.alias
+            .clone()
+            .unwrap_or_else(|| self.get_cairo_type_name())
+    }
+
     /// Maps a basic type to a built-in type that may already contains
     /// a `CairoType` implementation. If not, it's the name of the type itself.
     fn to_rust_or_cairo_builtin_type(&self) -> String {
@@ -32,7 +44,10 @@ impl AbiBasic {
             }
             "ClassHash" => format!("{}::ClassHash", CAIRO_TYPES_PATH),
             "EthAddress" => format!("{}::EthAddress", CAIRO_TYPES_PATH),
-            _ => s.clone(),
+            "u256" => format!("{}::U256", CAIRO_TYPES_PATH),
+            "bytes31" => format!("{}::Bytes31", CAIRO_TYPES_PATH),
+            "ByteArray" => "String".to_string(),
+            _ => self.get_rust_type_name(),
         }
     }
 }
@@ -97,6 +112,12 @@ impl AbiType for AbiBasic {
     fn to_rust_type_path(&self) -> String {
         self.to_rust_type()
     }
+
+    fn apply_aliases(&mut self, aliases: &HashMap<String, String>) {
+        if let Some(alias) = aliases.get(&self.cairo_type) {
+            self.alias = Some(alias.clone());
+        }
+    }
 }
 
 #[cfg(test)]
@@ -131,8 +152,40 @@ mod tests {
         let t = get_default();
         assert_eq!(t.to_rust_type_path(), "starknet::core::types::FieldElement");
     }
+    #[test]
+    fn to_rust_type_u256() {
+        let t = AbiBasic::new("core::integer::u256");
+        assert_eq!(
+            t.to_rust_type(),
+            "starknet_abigen_parser::cairo_types::U256"
+        );
+    }
+
+    #[test]
+    fn to_rust_type_byte_array() {
+        let t = AbiBasic::new("core::byte_array::ByteArray");
+        assert_eq!(t.to_rust_type(), "String");
+
+        let t = AbiBasic::new("core::bytes_31::bytes31");
+        assert_eq!(
+            t.to_rust_type(),
+            "starknet_abigen_parser::cairo_types::Bytes31"
+        );
+    }
+
     // TODO: add more tests for other built-in types.
 
+    #[test]
+    fn to_rust_type_alias() {
+        let mut t = AbiBasic::new("contract1::a::Position");
+        t.apply_aliases(&HashMap::from([(
+            "contract1::a::Position".to_string(),
+            "APosition".to_string(),
+        )]));
+        assert_eq!(t.get_cairo_type_name(), "Position");
+        assert_eq!(t.to_rust_type(), "APosition");
+    }
+
     #[test]
     fn from_string() {
         let t = AbiTypeAny::from_string("core::felt252");
@@ -0,0 +1,26 @@
+/// ABI type parsing result.
+pub type Result<T> = core::result::Result<T, ParseError>;
+
+/// An error raised while parsing a cairo type string from the ABI.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Invalid type {input:?} at position {position}: expected {expected}.")]
+pub struct ParseError {
+    /// The full type string being parsed.
+    pub input: String,
+    /// The position (in characters) of the offending character.
+    /// Equals to the input length if the input ended too early.
+    pub position: usize,
+    /// A description of the expected token at this position.
+    pub expected: String,
+}
+
+impl ParseError {
+    /// Initializes a new instance.
+    pub fn new(input: &str, position: usize, expected: &str) -> Self {
+        ParseError {
+            input: input.to_string(),
+            position,
+            expected: expected.to_string(),
+        }
+    }
+}
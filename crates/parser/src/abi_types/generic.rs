@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use super::{AbiType, AbiTypeAny, GENTY_FROZEN};
 
 #[derive(Debug, PartialEq, Clone)]
@@ -5,6 +7,7 @@ pub struct AbiGeneric {
     pub cairo_type: String,
     pub genty: String,
     pub inners: Vec<AbiTypeAny>,
+    pub alias: Option<String>,
 }
 
 impl AbiGeneric {
@@ -14,15 +17,24 @@ impl AbiGeneric {
             cairo_type: cairo_type.to_string(),
             genty: String::new(),
             inners,
+            alias: None,
         }
     }
 
+    /// Returns the name used for the type in rust. It's the cairo type
+    /// name, unless an alias was applied.
+    pub fn get_rust_type_name(&self) -> String {
+        self.alias
+            .clone()
+            .unwrap_or_else(|| self.get_cairo_type_name())
+    }
+
     /// Gets the definition of the type with it's generic types.
     pub fn get_rust_generic_def(&self, suffix: &str) -> String {
         let gentys = self.get_gentys_only();
         format!(
             "{}<{}{}>",
-            self.get_cairo_type_name(),
+            self.get_rust_type_name(),
             gentys.join(", "),
             suffix
         )
@@ -144,7 +156,7 @@ impl AbiType for AbiGeneric {
                 .collect::<Vec<_>>()
                 .join(", ");
 
-            format!("{}<{}>", self.get_cairo_type_name(), joined_inners)
+            format!("{}<{}>", self.get_rust_type_name(), joined_inners)
         }
     }
 
@@ -159,7 +171,17 @@ impl AbiType for AbiGeneric {
                 .collect::<Vec<_>>()
                 .join(", ");
 
-            format!("{}::<{}>", self.get_cairo_type_name(), joined_inners)
+            format!("{}::<{}>", self.get_rust_type_name(), joined_inners)
+        }
+    }
+
+    fn apply_aliases(&mut self, aliases: &HashMap<String, String>) {
+        if let Some(alias) = aliases.get(&self.cairo_type) {
+            self.alias = Some(alias.clone());
+        }
+
+        for inner in &mut self.inners {
+            inner.apply_aliases(aliases);
         }
     }
 }
@@ -225,6 +247,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn to_rust_type_alias() {
+        let mut t = AbiTypeAny::from_string("contract1::a::MyStruct::<contract1::a::Inner>");
+        t.apply_aliases(&HashMap::from([
+            (
+                "contract1::a::MyStruct".to_string(),
+                "AMyStruct".to_string(),
+            ),
+            ("contract1::a::Inner".to_string(), "AInner".to_string()),
+        ]));
+        assert_eq!(t.get_cairo_type_name(), "MyStruct");
+        assert_eq!(t.get_rust_type_name(), "AMyStruct");
+        assert_eq!(t.to_rust_type(), "AMyStruct<AInner>");
+        assert_eq!(t.to_rust_type_path(), "AMyStruct::<AInner>");
+    }
+
     #[test]
     fn from_string() {
         let t = AbiTypeAny::from_string("contract1::MyStruct::<core::felt252>");
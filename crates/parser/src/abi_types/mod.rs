@@ -2,9 +2,13 @@
 //!
 //! The idea of those types is to handle the parsing of any valid
 //! flatten cairo type that can also contain nested types.
-use std::iter::Peekable;
+use std::collections::HashMap;
+use std::iter::{Enumerate, Peekable};
 use std::str::Chars;
 
+mod error;
+pub use error::{ParseError, Result};
+
 pub mod basic;
 pub use basic::AbiBasic;
 
@@ -39,6 +43,9 @@ pub use tuple::AbiTuple;
 /// a match, it will be ignored.
 const GENTY_FROZEN: &str = "_";
 
+/// Characters of a type string being parsed, along with their position.
+type TypeChars<'a> = Peekable<Enumerate<Chars<'a>>>;
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum AbiTypeAny {
     Basic(AbiBasic),
@@ -78,6 +85,14 @@ pub trait AbiType {
     /// Get the rust type item path from the `AbiType`.
     /// This always includes all possible nested types and their genericity.
     fn to_rust_type_path(&self) -> String;
+
+    /// Applies the rust names to use for the given cairo type paths
+    /// (without generic arguments), including all possible nested types.
+    /// Aliases are used to avoid collisions between types with the
+    /// same name declared in different cairo modules.
+    ///
+    /// Does nothing by default, for types without any name to alias.
+    fn apply_aliases(&mut self, _aliases: &HashMap<String, String>) {}
 }
 
 impl AbiType for AbiTypeAny {
@@ -143,6 +158,15 @@ impl AbiType for AbiTypeAny {
             AbiTypeAny::Tuple(a) => a.to_rust_type_path(),
         }
     }
+
+    fn apply_aliases(&mut self, aliases: &HashMap<String, String>) {
+        match self {
+            AbiTypeAny::Basic(a) => a.apply_aliases(aliases),
+            AbiTypeAny::Array(a) => a.apply_aliases(aliases),
+            AbiTypeAny::Generic(a) => a.apply_aliases(aliases),
+            AbiTypeAny::Tuple(a) => a.apply_aliases(aliases),
+        }
+    }
 }
 
 /// Utils functions for `AbiTypeAny` to be called
@@ -163,92 +187,159 @@ impl AbiTypeAny {
         }
     }
 
+    /// Returns the cairo type path, without generic arguments.
+    pub fn get_cairo_type_path(&self) -> String {
+        match self {
+            Self::Basic(b) => b.get_cairo_type_full(),
+            Self::Array(a) => a.cairo_type.clone(),
+            Self::Generic(g) => g.cairo_type.clone(),
+            Self::Tuple(t) => t.get_cairo_type_full(),
+        }
+    }
+
+    /// Returns the name used for the type in rust. It's the cairo type
+    /// name, unless an alias was applied.
+    pub fn get_rust_type_name(&self) -> String {
+        match self {
+            Self::Basic(b) => b.get_rust_type_name(),
+            Self::Generic(g) => g.get_rust_type_name(),
+            _ => self.get_cairo_type_name(),
+        }
+    }
+
     /// Parses a string to build an `AbiTypeAny`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the string is not a valid cairo type.
+    /// Use `try_from_string` to handle the error instead.
     pub fn from_string(type_string: &str) -> Self {
-        let mut chars = type_string.chars().peekable();
-        Self::parse_type(&mut chars)
+        Self::try_from_string(type_string).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Parses a string to build an `AbiTypeAny`, returning
+    /// a `ParseError` if the string is not a valid cairo type.
+    pub fn try_from_string(type_string: &str) -> Result<Self> {
+        let mut chars = type_string.chars().enumerate().peekable();
+        let t = Self::parse_type(type_string, &mut chars)?;
+
+        // `parse_type` stops without consuming on closing tokens,
+        // which are not expected at the top level.
+        if let Some(&(pos, _)) = chars.peek() {
+            return Err(ParseError::new(type_string, pos, "end of type"));
+        }
+
+        Ok(t)
     }
 
     /// Parses any cairo type from the given string.
     /// This function handles the possible nested types.
-    fn parse_type(chars: &mut Peekable<Chars>) -> Self {
-        let mut generic_types = Vec::new();
+    fn parse_type(input: &str, chars: &mut TypeChars) -> Result<Self> {
+        let mut parsed: Option<Self> = None;
         let mut current_type = String::new();
 
-        while let Some(c) = chars.peek() {
+        while let Some(&(pos, c)) = chars.peek() {
             match c {
                 '<' => {
+                    if current_type.is_empty() {
+                        return Err(ParseError::new(input, pos, "type name before '<'"));
+                    }
+
                     chars.next();
                     // In cairo, a generic type is always preceeded by a separator "::".
                     let generic_type =
-                        Self::parse_generic(current_type.trim_end_matches("::"), chars);
-                    generic_types.push(generic_type);
+                        Self::parse_generic(input, current_type.trim_end_matches("::"), chars)?;
+                    parsed = Some(generic_type);
                     current_type.clear();
                 }
-                '>' => {
-                    break;
-                }
                 '(' => {
+                    if parsed.is_some() || !current_type.is_empty() {
+                        return Err(ParseError::new(input, pos, "',' or end of type"));
+                    }
+
                     chars.next();
-                    let tuple_type = Self::parse_tuple(chars);
-                    generic_types.push(tuple_type);
-                }
-                ')' => {
-                    break;
+                    parsed = Some(Self::parse_tuple(input, chars)?);
                 }
-                ',' => {
+                '>' | ')' | ',' => {
                     break;
                 }
                 ' ' => {
-                    // Ignore white spaces.
+                    // Ignore white spaces, which can't separate two names.
                     chars.next();
+
+                    if let Some(&(pos, next)) = chars.peek() {
+                        if !current_type.is_empty() && !"<>(), ".contains(next) {
+                            return Err(ParseError::new(input, pos, "',' or end of type"));
+                        }
+                    }
                 }
                 _ => {
-                    current_type.push(*c);
+                    // Only one type is expected, any other type must be separated by a ','.
+                    if parsed.is_some() {
+                        return Err(ParseError::new(input, pos, "',' or end of type"));
+                    }
+
+                    current_type.push(c);
                     chars.next();
                 }
             }
         }
 
         if !current_type.is_empty() {
-            generic_types.push(AbiTypeAny::Basic((&current_type).into()));
+            parsed = Some(AbiTypeAny::Basic((&current_type).into()));
         }
 
-        if generic_types.is_empty() {
-            // TODO: check if this one may be handled as Basic("()");
-            Self::Basic("()".into())
-        } else if generic_types.len() == 1 {
-            // Basic, Array or Generic with 1 inner type.
-            generic_types.pop().unwrap()
-        } else if chars.nth(0) == Some('(') {
-            // Tuple.
-            Self::Tuple(AbiTuple::new(generic_types))
-        } else {
-            unreachable!();
-        }
+        // TODO: check if this one may be handled as Basic("()");
+        Ok(parsed.unwrap_or_else(|| Self::Basic("()".into())))
     }
 
     /// Parses generic types detected between angle brackets.
-    fn parse_generic(current_type: &str, chars: &mut Peekable<Chars>) -> Self {
+    fn parse_generic(input: &str, current_type: &str, chars: &mut TypeChars) -> Result<Self> {
         let mut inners = vec![];
+        // An inner type is expected after '<' and after each ','.
+        let mut expect_inner = true;
 
-        while let Some(c) = chars.peek() {
-            match c {
-                '>' => {
+        let closing_pos = loop {
+            match chars.peek() {
+                Some(&(_, ' ')) => {
                     chars.next();
-                    break;
                 }
-                ',' => {
+                Some(&(pos, '>')) => {
+                    // A trailing ','.
+                    if expect_inner && !inners.is_empty() {
+                        return Err(ParseError::new(input, pos, "inner type"));
+                    }
+
                     chars.next();
+                    break pos;
                 }
-                _ => {
-                    inners.push(Self::parse_type(chars));
+                Some(&(pos, ',')) => {
+                    if expect_inner {
+                        return Err(ParseError::new(input, pos, "inner type"));
+                    }
+
+                    chars.next();
+                    expect_inner = true;
+                }
+                Some(&(pos, ')')) => {
+                    return Err(ParseError::new(input, pos, "'>'"));
+                }
+                Some(_) => {
+                    inners.push(Self::parse_type(input, chars)?);
+                    expect_inner = false;
+                }
+                None => {
+                    return Err(ParseError::new(input, input.chars().count(), "'>'"));
                 }
             }
-        }
+        };
 
         if inners.is_empty() {
-            panic!("Array/Span/Generic type expects at least one inner type");
+            return Err(ParseError::new(
+                input,
+                closing_pos,
+                "at least one inner type",
+            ));
         }
 
         // Array and Span are processed exactly the same, using `Vec`.
@@ -256,42 +347,198 @@ impl AbiTypeAny {
 
         if is_array {
             if inners.len() == 1 {
-                Self::Array(AbiArray::new(current_type, inners[0].clone()))
+                Ok(Self::Array(AbiArray::new(current_type, inners[0].clone())))
             } else {
-                panic!("Array/Span expect exactly one inner type");
+                Err(ParseError::new(
+                    input,
+                    closing_pos,
+                    "exactly one inner type for Array/Span",
+                ))
             }
         } else {
-            Self::Generic(AbiGeneric::new(current_type, inners))
+            Ok(Self::Generic(AbiGeneric::new(current_type, inners)))
         }
     }
 
     /// Parses a tuple, which can also contains nested types.
-    fn parse_tuple(chars: &mut Peekable<Chars>) -> Self {
+    fn parse_tuple(input: &str, chars: &mut TypeChars) -> Result<Self> {
         let mut tuple_values = Vec::new();
+        // An inner type is expected after each ',', except for the
+        // trailing ',' of a single element tuple like `(felt252,)`.
+        let mut expect_inner = false;
 
-        if chars.next_if(|&x| x == ')').is_some() {
-            return Self::Basic("()".into());
+        if chars.next_if(|&(_, x)| x == ')').is_some() {
+            return Ok(Self::Basic("()".into()));
         }
 
-        while let Some(c) = chars.peek() {
-            match c {
-                ' ' => {
+        loop {
+            match chars.peek() {
+                Some(&(_, ' ')) => {
                     chars.next();
                 }
-                ',' => {
+                Some(&(pos, ',')) => {
+                    if expect_inner || tuple_values.is_empty() {
+                        return Err(ParseError::new(input, pos, "inner type"));
+                    }
+
                     chars.next();
+                    expect_inner = true;
                 }
-                ')' => {
+                Some(&(pos, ')')) => {
+                    if expect_inner && tuple_values.len() > 1 {
+                        return Err(ParseError::new(input, pos, "inner type"));
+                    }
+
                     chars.next();
                     break;
                 }
-                _ => {
-                    let v = Self::parse_type(chars);
-                    tuple_values.push(v);
+                Some(&(pos, '>')) => {
+                    return Err(ParseError::new(input, pos, "')'"));
+                }
+                Some(_) => {
+                    tuple_values.push(Self::parse_type(input, chars)?);
+                    expect_inner = false;
+                }
+                None => {
+                    return Err(ParseError::new(input, input.chars().count(), "')'"));
                 }
             }
         }
 
-        Self::Tuple(AbiTuple::new(tuple_values))
+        Ok(Self::Tuple(AbiTuple::new(tuple_values)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn try_from_string_valid() {
+        let t = AbiTypeAny::try_from_string("core::array::Span::<(core::felt252, core::bool)>");
+        assert!(t.is_ok());
+    }
+
+    #[test]
+    fn try_from_string_unclosed_generic() {
+        let e = AbiTypeAny::try_from_string("core::array::Array::<core::felt252").unwrap_err();
+        assert_eq!(e.input, "core::array::Array::<core::felt252");
+        assert_eq!(e.position, 34);
+        assert_eq!(e.expected, "'>'");
+    }
+
+    #[test]
+    fn try_from_string_unclosed_tuple() {
+        let e = AbiTypeAny::try_from_string("(core::felt252, core::integer::u32").unwrap_err();
+        assert_eq!(e.position, 34);
+        assert_eq!(e.expected, "')'");
+    }
+
+    #[test]
+    fn try_from_string_mismatched_closing() {
+        let e = AbiTypeAny::try_from_string("contract1::MyStruct::<core::felt252)").unwrap_err();
+        assert_eq!(e.position, 35);
+        assert_eq!(e.expected, "'>'");
+
+        let e = AbiTypeAny::try_from_string("(core::felt252>").unwrap_err();
+        assert_eq!(e.position, 14);
+        assert_eq!(e.expected, "')'");
+    }
+
+    #[test]
+    fn try_from_string_empty_generic() {
+        let e = AbiTypeAny::try_from_string("contract1::MyStruct::<>").unwrap_err();
+        assert_eq!(e.position, 22);
+        assert_eq!(e.expected, "at least one inner type");
+    }
+
+    #[test]
+    fn try_from_string_blank_generic() {
+        let e = AbiTypeAny::try_from_string("contract1::MyStruct::< >").unwrap_err();
+        assert_eq!(e.position, 23);
+        assert_eq!(e.expected, "at least one inner type");
+    }
+
+    #[test]
+    fn try_from_string_generic_empty_inner() {
+        let e = AbiTypeAny::try_from_string("contract1::MyStruct::<core::felt252,,core::u8>")
+            .unwrap_err();
+        assert_eq!(e.position, 36);
+        assert_eq!(e.expected, "inner type");
+
+        let e = AbiTypeAny::try_from_string("contract1::MyStruct::<,core::felt252>").unwrap_err();
+        assert_eq!(e.position, 22);
+        assert_eq!(e.expected, "inner type");
+    }
+
+    #[test]
+    fn try_from_string_tuple_empty_inner() {
+        let e = AbiTypeAny::try_from_string("(core::felt252,,core::u8)").unwrap_err();
+        assert_eq!(e.position, 15);
+        assert_eq!(e.expected, "inner type");
+
+        let e = AbiTypeAny::try_from_string("(,)").unwrap_err();
+        assert_eq!(e.position, 1);
+        assert_eq!(e.expected, "inner type");
+
+        let e = AbiTypeAny::try_from_string("(core::felt252, core::u8,)").unwrap_err();
+        assert_eq!(e.position, 25);
+        assert_eq!(e.expected, "inner type");
+
+        // Single element tuple.
+        let t = AbiTypeAny::try_from_string("(core::felt252,)").unwrap();
+        assert_eq!(t.get_cairo_type_full(), "(core::felt252)");
+    }
+
+    #[test]
+    fn try_from_string_space_separated_names() {
+        let e = AbiTypeAny::try_from_string("core::felt252 core::u8").unwrap_err();
+        assert_eq!(e.position, 14);
+        assert_eq!(e.expected, "',' or end of type");
+
+        let e = AbiTypeAny::try_from_string("(core::felt252 core::u8)").unwrap_err();
+        assert_eq!(e.position, 15);
+        assert_eq!(e.expected, "',' or end of type");
+
+        assert!(AbiTypeAny::try_from_string(" core::felt252 ").is_ok());
+    }
+
+    #[test]
+    fn try_from_string_generic_trailing_comma() {
+        let e = AbiTypeAny::try_from_string("contract1::MyStruct::<core::felt252, >").unwrap_err();
+        assert_eq!(e.position, 37);
+        assert_eq!(e.expected, "inner type");
+    }
+
+    #[test]
+    fn try_from_string_array_multiple_inners() {
+        let e = AbiTypeAny::try_from_string("core::array::Array::<core::felt252, core::felt252>")
+            .unwrap_err();
+        assert_eq!(e.position, 49);
+        assert_eq!(e.expected, "exactly one inner type for Array/Span");
+    }
+
+    #[test]
+    fn try_from_string_trailing() {
+        let e = AbiTypeAny::try_from_string("core::felt252>").unwrap_err();
+        assert_eq!(e.position, 13);
+        assert_eq!(e.expected, "end of type");
+
+        let e = AbiTypeAny::try_from_string("contract1::MyStruct::<core::felt252>abc").unwrap_err();
+        assert_eq!(e.position, 36);
+        assert_eq!(e.expected, "',' or end of type");
+    }
+
+    #[test]
+    fn try_from_string_missing_name() {
+        let e = AbiTypeAny::try_from_string("<core::felt252>").unwrap_err();
+        assert_eq!(e.position, 0);
+        assert_eq!(e.expected, "type name before '<'");
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid type")]
+    fn from_string_panics() {
+        AbiTypeAny::from_string("core::array::Array::<core::felt252");
     }
 }
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use super::{AbiType, AbiTypeAny, GENTY_FROZEN};
 
 #[derive(Debug, PartialEq, Clone)]
@@ -122,6 +124,12 @@ impl AbiType for AbiTuple {
             s
         }
     }
+
+    fn apply_aliases(&mut self, aliases: &HashMap<String, String>) {
+        for inner in &mut self.inners {
+            inner.apply_aliases(aliases);
+        }
+    }
 }
 
 #[cfg(test)]
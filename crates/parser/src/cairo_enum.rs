@@ -16,7 +16,7 @@ pub struct CairoEnum {
 impl CairoEnum {
     /// Gets the name of the enum type.
     pub fn get_name(&self) -> String {
-        self.abi.get_cairo_type_name()
+        self.abi.get_rust_type_name()
     }
 
     /// Returns true if the enum is generic, false otherwise.
@@ -78,4 +78,13 @@ impl CairoEnum {
             }
         }
     }
+
+    /// Applies the rust names aliases to the enum and it's variants.
+    pub fn apply_aliases(&mut self, aliases: &HashMap<String, String>) {
+        self.abi.apply_aliases(aliases);
+
+        for (_, abi) in &mut self.variants {
+            abi.apply_aliases(aliases);
+        }
+    }
 }
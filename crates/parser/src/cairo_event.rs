@@ -1,9 +1,102 @@
 //! Event parsing.
-use starknet::core::types::contract::{AbiEvent, AbiNamedMember, EventFieldKind, TypedAbiEvent};
+use starknet::core::types::contract::{
+    AbiEvent, AbiNamedMember, EventFieldKind as AbiEventFieldKind, TypedAbiEvent,
+};
+use starknet::core::types::FieldElement;
+use std::collections::HashMap;
 
 use super::abi_types::{AbiType, AbiTypeAny};
+use super::cairo_types::Error as CairoError;
 use super::{CairoEnum, CairoStruct};
 
+/// An error raised while decoding an event from it's keys and data.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum EventError {
+    /// The selector doesn't match any known event, the event is
+    /// likely emitted by an other contract.
+    #[error("Could not match any event from selector {selector:#064x}.")]
+    UnknownSelector { selector: FieldElement },
+    /// The selector of `event` was expected at `offset` in the keys.
+    #[error("Missing event selector for {event} at key offset {offset}.")]
+    MissingKeys { event: String, offset: usize },
+    /// The selector is known, but a field of the event failed to deserialize.
+    #[error("Could not deserialize field {field} for {event}: {source}")]
+    Field {
+        event: String,
+        field: String,
+        source: CairoError,
+    },
+    /// The event was decoded, but some keys or data were not consumed.
+    #[error("Event {event} was decoded with {keys} key(s) and {data} data felt(s) remaining.")]
+    TrailingFelts {
+        event: String,
+        keys: usize,
+        data: usize,
+    },
+}
+
+/// How strictly events are decoded from their keys and data.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum EventDecodingMode {
+    /// Keys or data remaining once the event is decoded are an error.
+    Strict,
+    /// Keys or data remaining once the event is decoded are ignored.
+    #[default]
+    Lenient,
+}
+
+impl EventDecodingMode {
+    /// Checks that the keys and data were entirely consumed while
+    /// decoding `event`, returning `EventError::TrailingFelts` otherwise.
+    /// Always succeeds in lenient mode.
+    pub fn check_consumed(
+        &self,
+        event: &str,
+        keys: &[FieldElement],
+        key_offset: usize,
+        data: &[FieldElement],
+        data_offset: usize,
+    ) -> Result<(), EventError> {
+        if *self == EventDecodingMode::Lenient
+            || (key_offset >= keys.len() && data_offset >= data.len())
+        {
+            return Ok(());
+        }
+
+        Err(EventError::TrailingFelts {
+            event: event.to_string(),
+            keys: keys.len().saturating_sub(key_offset),
+            data: data.len().saturating_sub(data_offset),
+        })
+    }
+}
+
+/// Kind of an event member (struct event) or variant (enum event).
+///
+/// `starknet-core` doesn't support the `flat` kind, which is
+/// then set on the event once parsed with `set_flat_fields`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum EventFieldKind {
+    /// Serialized into the event keys.
+    Key,
+    /// Serialized into the event data.
+    Data,
+    /// Nested event, the variant selector is added to the keys.
+    Nested,
+    /// Flattened event, the variant selector is omitted.
+    Flat,
+}
+
+impl From<&AbiEventFieldKind> for EventFieldKind {
+    fn from(kind: &AbiEventFieldKind) -> Self {
+        match kind {
+            AbiEventFieldKind::Key => EventFieldKind::Key,
+            AbiEventFieldKind::Data => EventFieldKind::Data,
+            AbiEventFieldKind::Nested => EventFieldKind::Nested,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum CairoEventInner {
     Enum(CairoEnum),
@@ -20,9 +113,42 @@ pub struct CairoEvent {
 impl CairoEvent {
     /// Gets the name of the struct type.
     pub fn get_name(&self) -> String {
+        self.abi.get_rust_type_name()
+    }
+
+    /// Gets the name of the event as declared in cairo.
+    pub fn get_cairo_name(&self) -> String {
         self.abi.get_cairo_type_name()
     }
 
+    /// Applies the rust names aliases to the event and it's inner type.
+    pub fn apply_aliases(&mut self, aliases: &HashMap<String, String>) {
+        self.abi.apply_aliases(aliases);
+
+        match &mut self.inner {
+            CairoEventInner::Enum(e) => e.apply_aliases(aliases),
+            CairoEventInner::Struct(s) => s.apply_aliases(aliases),
+        }
+    }
+
+    /// Gets the names of the members/variants, in the same
+    /// order as `fields_kinds`.
+    pub fn get_fields_names(&self) -> Vec<String> {
+        match &self.inner {
+            CairoEventInner::Enum(e) => e.variants.iter().map(|(n, _)| n.clone()).collect(),
+            CairoEventInner::Struct(s) => s.members.iter().map(|(n, _)| n.clone()).collect(),
+        }
+    }
+
+    /// Sets the kind of the given members/variants to `Flat`.
+    pub fn set_flat_fields(&mut self, names: &[String]) {
+        for (idx, name) in self.get_fields_names().iter().enumerate() {
+            if names.contains(name) {
+                self.fields_kinds[idx] = EventFieldKind::Flat;
+            }
+        }
+    }
+
     /// Gets the count for each field kind (keys, data).
     pub fn count_fields_kinds(&self) -> (usize, usize) {
         let mut k = 0;
@@ -54,7 +180,7 @@ impl CairoEvent {
                         .members
                         .iter()
                         .map(|m| {
-                            kinds.push(m.kind.clone());
+                            kinds.push(EventFieldKind::from(&m.kind));
                             AbiNamedMember {
                                 name: m.name.clone(),
                                 r#type: m.r#type.clone(),
@@ -81,7 +207,7 @@ impl CairoEvent {
                         .variants
                         .iter()
                         .map(|v| {
-                            kinds.push(v.kind.clone());
+                            kinds.push(EventFieldKind::from(&v.kind));
                             AbiNamedMember {
                                 name: v.name.clone(),
                                 r#type: v.r#type.clone(),
@@ -98,11 +224,151 @@ impl CairoEvent {
                     })
                 }
             },
-            AbiEvent::Untyped(_) => {
-                // Can we support this..?
-                //panic!("Untyped events are not supported");
-                None
+            AbiEvent::Untyped(e) => {
+                // Untyped events have their selector as only key,
+                // all the inputs being serialized into the data.
+                let name = &e.name;
+                let kinds = vec![EventFieldKind::Data; e.inputs.len()];
+                let cs = CairoStruct::new(name, &e.inputs);
+
+                Some(CairoEvent {
+                    abi: AbiTypeAny::from_string(name),
+                    inner: CairoEventInner::Struct(cs),
+                    fields_kinds: kinds,
+                })
             }
         }
     }
+
+    /// Initializes a new `Event` enum with a nested variant for each
+    /// of the given events, named as the event.
+    ///
+    /// Untyped events are not grouped into an enum in the ABI, but they
+    /// are emitted with the selector of their name as first key, which
+    /// is the layout of a nested variant.
+    pub fn new_event_enum(name: &str, events: &[&CairoEvent]) -> CairoEvent {
+        let variants: Vec<AbiNamedMember> = events
+            .iter()
+            .map(|ev| AbiNamedMember {
+                name: ev.get_cairo_name(),
+                r#type: ev.abi.get_cairo_type_full(),
+            })
+            .collect();
+
+        CairoEvent {
+            abi: AbiTypeAny::from_string(name),
+            inner: CairoEventInner::Enum(CairoEnum::new(name, &variants)),
+            fields_kinds: vec![EventFieldKind::Nested; variants.len()],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starknet::core::types::contract::{AbiEventEnum, EventField, UntypedAbiEvent};
+
+    fn get_event_enum() -> AbiEvent {
+        let variant = |name: &str, r#type: &str| EventField {
+            name: name.to_string(),
+            r#type: r#type.to_string(),
+            kind: AbiEventFieldKind::Nested,
+        };
+
+        AbiEvent::Typed(TypedAbiEvent::Enum(AbiEventEnum {
+            name: "contract::Event".to_string(),
+            variants: vec![
+                variant("OwnableEvent", "ownable::Event"),
+                variant("Transfer", "contract::Transfer"),
+            ],
+        }))
+    }
+
+    #[test]
+    fn test_event_error_display() {
+        let e = EventError::UnknownSelector {
+            selector: FieldElement::ONE,
+        };
+        assert_eq!(
+            e.to_string(),
+            "Could not match any event from selector 0x0000000000000000000000000000000000000000000000000000000000000001."
+        );
+
+        let e = EventError::Field {
+            event: "Transfer".to_string(),
+            field: "amount".to_string(),
+            source: CairoError::Deserialize("bad".to_string()),
+        };
+        assert_eq!(
+            e.to_string(),
+            "Could not deserialize field amount for Transfer: Error during deserialization \"bad\"."
+        );
+    }
+
+    #[test]
+    fn test_event_decoding_mode_check_consumed() {
+        let keys = vec![FieldElement::ONE, FieldElement::TWO];
+        let data = vec![FieldElement::THREE];
+
+        let strict = EventDecodingMode::Strict;
+        assert!(strict.check_consumed("E", &keys, 2, &data, 1).is_ok());
+        assert_eq!(
+            strict.check_consumed("E", &keys, 1, &data, 0).unwrap_err(),
+            EventError::TrailingFelts {
+                event: "E".to_string(),
+                keys: 1,
+                data: 1,
+            }
+        );
+
+        let lenient = EventDecodingMode::Lenient;
+        assert!(lenient.check_consumed("E", &keys, 1, &data, 0).is_ok());
+        assert_eq!(EventDecodingMode::default(), lenient);
+    }
+
+    #[test]
+    fn test_event_fields_kinds() {
+        let cev = CairoEvent::new(&get_event_enum()).unwrap();
+        assert_eq!(cev.get_fields_names(), vec!["OwnableEvent", "Transfer"]);
+        assert_eq!(
+            cev.fields_kinds,
+            vec![EventFieldKind::Nested, EventFieldKind::Nested]
+        );
+    }
+
+    #[test]
+    fn test_event_set_flat_fields() {
+        let mut cev = CairoEvent::new(&get_event_enum()).unwrap();
+        cev.set_flat_fields(&["OwnableEvent".to_string()]);
+        assert_eq!(
+            cev.fields_kinds,
+            vec![EventFieldKind::Flat, EventFieldKind::Nested]
+        );
+    }
+
+    #[test]
+    fn test_untyped_event() {
+        let member = |name: &str| AbiNamedMember {
+            name: name.to_string(),
+            r#type: "core::felt252".to_string(),
+        };
+
+        let ev = AbiEvent::Untyped(UntypedAbiEvent {
+            name: "Transfer".to_string(),
+            inputs: vec![member("from"), member("to")],
+        });
+
+        let cev = CairoEvent::new(&ev).unwrap();
+        assert_eq!(cev.get_name(), "Transfer");
+        assert_eq!(cev.get_fields_names(), vec!["from", "to"]);
+        assert_eq!(
+            cev.fields_kinds,
+            vec![EventFieldKind::Data, EventFieldKind::Data]
+        );
+
+        let enum_ev = CairoEvent::new_event_enum("Event", &[&cev]);
+        assert_eq!(enum_ev.get_name(), "Event");
+        assert_eq!(enum_ev.get_fields_names(), vec!["Transfer"]);
+        assert_eq!(enum_ev.fields_kinds, vec![EventFieldKind::Nested]);
+    }
 }
@@ -1,16 +1,14 @@
 use starknet::core::types::contract::{AbiNamedMember, AbiOutput, StateMutability};
+use std::collections::HashMap;
 
-use super::abi_types::AbiTypeAny;
+use super::abi_types::{AbiTuple, AbiType, AbiTypeAny};
 
 #[derive(Debug, Clone)]
 pub struct CairoFunction {
     pub name: String,
     pub state_mutability: StateMutability,
     pub inputs: Vec<(String, AbiTypeAny)>,
-    // For now, only one output type is supported (or none).
-    // TODO: investigate the cases where more than one output is
-    // present in the ABI.
-    pub output: Option<AbiTypeAny>,
+    pub outputs: Vec<AbiTypeAny>,
 }
 
 impl CairoFunction {
@@ -19,17 +17,14 @@ impl CairoFunction {
         abi_name: &str,
         state_mutability: StateMutability,
         inputs: &[AbiNamedMember],
-        outputs: &Vec<AbiOutput>,
+        outputs: &[AbiOutput],
     ) -> CairoFunction {
         let name = abi_name.to_string();
 
-        let output = if !outputs.is_empty() {
-            // For now, only first output is considered.
-            // TODO: investigate when we can have several outputs.
-            Some(AbiTypeAny::from_string(&outputs[0].r#type))
-        } else {
-            None
-        };
+        let outputs = outputs
+            .iter()
+            .map(|o| AbiTypeAny::from_string(&o.r#type))
+            .collect();
 
         let inputs = inputs
             .iter()
@@ -40,7 +35,75 @@ impl CairoFunction {
             name,
             state_mutability,
             inputs,
-            output,
+            outputs,
+        }
+    }
+
+    /// Gets the type returned by the function: `None` without outputs,
+    /// the output type for a single output, and a tuple of the outputs
+    /// types otherwise, which is deserialized as the outputs one after
+    /// the other.
+    ///
+    /// The ABI doesn't name the outputs, legacy ABIs outputs being
+    /// already grouped into a named struct when converted.
+    pub fn get_output(&self) -> Option<AbiTypeAny> {
+        match self.outputs.len() {
+            0 => None,
+            1 => Some(self.outputs[0].clone()),
+            _ => Some(AbiTypeAny::Tuple(AbiTuple::new(self.outputs.clone()))),
         }
     }
+
+    /// Applies the rust names aliases to the inputs and outputs types.
+    pub fn apply_aliases(&mut self, aliases: &HashMap<String, String>) {
+        for (_, abi) in &mut self.inputs {
+            abi.apply_aliases(aliases);
+        }
+
+        for o in &mut self.outputs {
+            o.apply_aliases(aliases);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn output(r#type: &str) -> AbiOutput {
+        AbiOutput {
+            r#type: r#type.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_function_outputs() {
+        let f = CairoFunction::new("f", StateMutability::View, &[], &[]);
+        assert_eq!(f.get_output(), None);
+
+        let f = CairoFunction::new("f", StateMutability::View, &[], &[output("core::felt252")]);
+        assert_eq!(
+            f.get_output(),
+            Some(AbiTypeAny::Basic("core::felt252".into()))
+        );
+    }
+
+    #[test]
+    fn test_function_multiple_outputs() {
+        let f = CairoFunction::new(
+            "f",
+            StateMutability::View,
+            &[],
+            &[output("core::felt252"), output("core::integer::u256")],
+        );
+
+        assert_eq!(f.outputs.len(), 2);
+        assert_eq!(
+            f.get_output(),
+            Some(AbiTypeAny::Tuple(AbiTuple::new(vec![
+                AbiTypeAny::Basic("core::felt252".into()),
+                AbiTypeAny::Basic("core::integer::u256".into()),
+            ])))
+        );
+    }
 }
@@ -15,7 +15,7 @@ pub struct CairoStruct {
 impl CairoStruct {
     /// Gets the name of the struct type.
     pub fn get_name(&self) -> String {
-        self.abi.get_cairo_type_name()
+        self.abi.get_rust_type_name()
     }
 
     /// Returns true if the struct is generic, false otherwise.
@@ -77,4 +77,13 @@ impl CairoStruct {
             }
         }
     }
+
+    /// Applies the rust names aliases to the struct and it's members.
+    pub fn apply_aliases(&mut self, aliases: &HashMap<String, String>) {
+        self.abi.apply_aliases(aliases);
+
+        for (_, abi) in &mut self.members {
+            abi.apply_aliases(aliases);
+        }
+    }
 }
@@ -14,18 +14,93 @@ pub enum Error {
     Serialize(String),
     #[error("Error during deserialization {0:?}.")]
     Deserialize(String),
+    #[error("Error during parsing {0:?}.")]
+    Parse(String),
+    #[error("Out of felts: {expected} felt(s) expected at offset {offset}, but only {available} available.")]
+    OutOfFelts {
+        expected: usize,
+        available: usize,
+        offset: usize,
+    },
+    #[error("Error during deserialization of {type_name}{path} at offset {offset}: {source}")]
+    Context {
+        /// Name of the outermost type being deserialized.
+        type_name: String,
+        /// Path from the outermost type to the failing value,
+        /// like `.items[3].price.low`.
+        path: String,
+        /// Offset of the felt where the failing value starts.
+        offset: usize,
+        /// The underlying error.
+        source: Box<Error>,
+    },
+}
+
+impl Error {
+    /// Wraps the error with the path segment of a member (`.name`)
+    /// or variant (`::Name`) of the type `type_name`.
+    ///
+    /// If the error already carries a context, the segment is prepended
+    /// to the existing path, and the innermost offset is kept.
+    pub fn with_context(self, type_name: &str, segment: &str, offset: usize) -> Self {
+        match self {
+            Error::Context {
+                path,
+                offset,
+                source,
+                ..
+            } => Error::Context {
+                type_name: type_name.to_string(),
+                path: format!("{}{}", segment, path),
+                offset,
+                source,
+            },
+            e => Error::Context {
+                type_name: type_name.to_string(),
+                path: segment.to_string(),
+                offset,
+                source: Box::new(e),
+            },
+        }
+    }
+
+    /// Wraps the error with the index of the array element that failed.
+    ///
+    /// The type name of an existing context is kept, as arrays have no name.
+    pub fn with_index(self, index: usize, offset: usize) -> Self {
+        let type_name = match &self {
+            Error::Context { type_name, .. } => type_name.clone(),
+            _ => "Vec".to_string(),
+        };
+
+        self.with_context(&type_name, &format!("[{}]", index), offset)
+    }
+
+    /// Returns the underlying error, without any context.
+    pub fn root_cause(&self) -> &Error {
+        match self {
+            Error::Context { source, .. } => source.root_cause(),
+            e => e,
+        }
+    }
 }
 
 impl CairoType for Error {
     type RustType = Self;
 
+    const SERIALIZED_SIZE: Option<usize> = Some(0);
+
     fn serialize(_rust: &Self::RustType) -> Vec<FieldElement> {
         vec![]
     }
 
-    fn deserialize(_felts: &[FieldElement], _offset: usize) -> Result<Self::RustType> {
-        Ok(Error::Deserialize(
-            "Error cairotype deserialized?".to_string(),
+    fn deserialize_with_size(
+        _felts: &[FieldElement],
+        _offset: usize,
+    ) -> Result<(Self::RustType, usize)> {
+        Ok((
+            Error::Deserialize("Error cairotype deserialized?".to_string()),
+            0,
         ))
     }
 }
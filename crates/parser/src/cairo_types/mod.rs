@@ -10,7 +10,10 @@ mod error;
 pub use error::{Error, Result};
 
 pub mod types;
+pub use types::byte_array::ByteArray;
+pub use types::bytes_31::Bytes31;
 pub use types::starknet::*;
+pub use types::u256::U256;
 pub use types::*;
 
 use ::starknet::core::types::FieldElement;
@@ -19,7 +22,14 @@ pub const CAIRO_TYPES_PATH: &str = "starknet_abigen_parser::cairo_types";
 
 /// Basic cairo structs that are already implemented inside
 /// this crate and hence skipped during ABI generation.
-pub const CAIRO_BASIC_STRUCTS: [&str; 4] = ["Span", "ClassHash", "ContractAddress", "EthAddress"];
+pub const CAIRO_BASIC_STRUCTS: [&str; 6] = [
+    "Span",
+    "ClassHash",
+    "ContractAddress",
+    "EthAddress",
+    "u256",
+    "ByteArray",
+];
 
 /// Same as `CAIRO_BASIC_STRUCTS`, but for enums.
 pub const CAIRO_BASIC_ENUMS: [&str; 3] = ["Option", "Result", "bool"];
@@ -48,9 +58,105 @@ pub trait CairoType {
     /// Serializes the given type into a FieldElement sequence.
     fn serialize(rust: &Self::RustType) -> Vec<FieldElement>;
 
-    /// TODO: add serialize_to(rust: &Self::RustType, out: &mut Vec<FieldElement>)
-    /// for large buffers optimization.
+    /// Serializes the given type, appending the felts to `out`.
+    /// Nested types should be serialized with this function to
+    /// avoid allocating a new buffer at each level.
+    #[inline]
+    fn serialize_to(rust: &Self::RustType, out: &mut Vec<FieldElement>) {
+        out.extend(Self::serialize(rust));
+    }
+
+    /// Deserializes an array of felts into the given type, starting at `offset`.
+    /// Returns the deserialized value along with the number of felts consumed,
+    /// which avoids re-walking the value with `serialized_size` to advance
+    /// to the next one.
+    fn deserialize_with_size(
+        felts: &[FieldElement],
+        offset: usize,
+    ) -> Result<(Self::RustType, usize)>;
 
     /// Deserializes an array of felts into the given type.
-    fn deserialize(felts: &[FieldElement], offset: usize) -> Result<Self::RustType>;
+    #[inline]
+    fn deserialize(felts: &[FieldElement], offset: usize) -> Result<Self::RustType> {
+        Self::deserialize_with_size(felts, offset).map(|(rust, _)| rust)
+    }
+}
+
+/// Checks that at least `expected` felts are available from `offset`,
+/// returning an `Error::OutOfFelts` otherwise.
+#[inline]
+pub fn check_felts_len(felts: &[FieldElement], offset: usize, expected: usize) -> Result<()> {
+    match offset.checked_add(expected) {
+        Some(end) if end <= felts.len() => Ok(()),
+        _ => Err(Error::OutOfFelts {
+            expected,
+            available: felts.len().saturating_sub(offset),
+            offset,
+        }),
+    }
+}
+
+/// Gets the felt at the given offset, returning an `Error::OutOfFelts`
+/// if the offset is out of bounds.
+#[inline]
+pub fn get_felt(felts: &[FieldElement], offset: usize) -> Result<FieldElement> {
+    check_felts_len(felts, offset, 1)?;
+    Ok(felts[offset])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_felt_out_of_felts() {
+        let felts = vec![FieldElement::ONE];
+        assert_eq!(get_felt(&felts, 0).unwrap(), FieldElement::ONE);
+        assert_eq!(
+            get_felt(&felts, 1).unwrap_err(),
+            Error::OutOfFelts {
+                expected: 1,
+                available: 0,
+                offset: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_check_felts_len() {
+        let felts = vec![FieldElement::ONE, FieldElement::TWO];
+        assert!(check_felts_len(&felts, 0, 2).is_ok());
+        assert_eq!(
+            check_felts_len(&felts, 1, 3).unwrap_err(),
+            Error::OutOfFelts {
+                expected: 3,
+                available: 1,
+                offset: 1
+            }
+        );
+        assert!(check_felts_len(&felts, usize::MAX, 2).is_err());
+    }
+
+    #[test]
+    fn test_error_with_context() {
+        let e = Error::Deserialize("bad".to_string())
+            .with_context("Price", ".low", 7)
+            .with_index(3, 5)
+            .with_context("Order", ".items", 2);
+
+        assert_eq!(
+            e,
+            Error::Context {
+                type_name: "Order".to_string(),
+                path: ".items[3].low".to_string(),
+                offset: 7,
+                source: Box::new(Error::Deserialize("bad".to_string())),
+            }
+        );
+        assert_eq!(e.root_cause(), &Error::Deserialize("bad".to_string()));
+        assert_eq!(
+            e.to_string(),
+            "Error during deserialization of Order.items[3].low at offset 7: Error during deserialization \"bad\"."
+        );
+    }
 }
@@ -1,6 +1,6 @@
 //! CairoType implementation for `Vec`.
 //! They are used for Array and Span cairo types.
-use crate::cairo_types::{CairoType, Error, Result};
+use crate::cairo_types::{check_felts_len, CairoType, Error, Result};
 use starknet::core::types::FieldElement;
 
 impl<T, RT> CairoType for Vec<T>
@@ -19,31 +19,56 @@ where
     }
 
     fn serialize(rust: &Self::RustType) -> Vec<FieldElement> {
-        let mut out: Vec<FieldElement> = vec![rust.len().into()];
-        rust.iter().for_each(|r| out.extend(T::serialize(r)));
+        let mut out = Vec::with_capacity(Self::serialized_size(rust));
+        Self::serialize_to(rust, &mut out);
         out
     }
 
-    fn deserialize(felts: &[FieldElement], offset: usize) -> Result<Self::RustType> {
-        let len: usize = usize::from_str_radix(format!("{:x}", felts[offset]).as_str(), 16)
-            .map_err(|_| {
-                Error::Deserialize("First felt of an array must fit into usize".to_string())
-            })?;
+    fn serialize_to(rust: &Self::RustType, out: &mut Vec<FieldElement>) {
+        out.push(rust.len().into());
+        rust.iter().for_each(|r| T::serialize_to(r, out));
+    }
 
-        let mut out: Vec<RT> = vec![];
+    fn deserialize_with_size(
+        felts: &[FieldElement],
+        offset: usize,
+    ) -> Result<(Self::RustType, usize)> {
+        let len = usize::deserialize(felts, offset)?;
+        let start = offset;
         let mut offset = offset + 1;
 
+        // For fixed size elements, the length can be checked upfront
+        // to reject a length prefix claiming more elements than remaining felts.
+        if let Some(size) = T::SERIALIZED_SIZE {
+            check_felts_len(felts, offset, len.saturating_mul(size))?;
+        }
+
+        let mut out: Vec<RT> = vec![];
+
         loop {
             if out.len() == len {
                 break;
             }
 
-            let rust: RT = T::deserialize(felts, offset)?;
-            offset += T::serialized_size(&rust);
+            let (rust, size): (RT, usize) = T::deserialize_with_size(felts, offset)
+                .map_err(|e| e.with_index(out.len(), offset))?;
+
+            // Zero sized elements (like structs without members) don't consume
+            // any felt, the length is then bounded by the felts count to not
+            // loop on a forged length.
+            if size == 0 && len > felts.len() {
+                return Err(Error::Deserialize(format!(
+                    "Array length {} of zero sized elements exceeds the {} felts",
+                    len,
+                    felts.len()
+                )));
+            }
+
+            offset += size;
             out.push(rust);
         }
 
-        Ok(out)
+        Ok((out, offset - start))
     }
 }
 
@@ -76,6 +101,123 @@ mod tests {
         assert_eq!(vals[1], 9988_u32);
     }
 
+    #[test]
+    fn test_deserialize_array_out_of_felts() {
+        let felts: Vec<FieldElement> = vec![FieldElement::THREE, FieldElement::ONE];
+        assert_eq!(
+            Vec::<u32>::deserialize(&felts, 0).unwrap_err(),
+            Error::OutOfFelts {
+                expected: 3,
+                available: 1,
+                offset: 1
+            }
+        );
+
+        // Dynamic elements are checked while deserializing.
+        let felts: Vec<FieldElement> =
+            vec![FieldElement::TWO, FieldElement::ONE, FieldElement::ONE];
+        assert_eq!(
+            Vec::<Vec<u32>>::deserialize(&felts, 0)
+                .unwrap_err()
+                .root_cause(),
+            &Error::OutOfFelts {
+                expected: 1,
+                available: 0,
+                offset: 3
+            }
+        );
+
+        assert!(Vec::<u32>::deserialize(&[], 0).is_err());
+    }
+
+    #[test]
+    fn test_deserialize_array_zero_sized() {
+        let felts = vec![FieldElement::TWO, FieldElement::ONE];
+        assert_eq!(Vec::<()>::deserialize(&felts, 0).unwrap(), vec![(), ()]);
+        assert_eq!(Vec::<()>::deserialize_with_size(&felts, 0).unwrap().1, 1);
+
+        let felts = vec![FieldElement::from(u32::MAX)];
+        assert!(matches!(
+            Vec::<()>::deserialize(&felts, 0),
+            Err(Error::Deserialize(_))
+        ));
+        assert!(Vec::<Error>::deserialize(&felts, 0).is_err());
+    }
+
+    #[test]
+    fn test_deserialize_array_error_path() {
+        let felts: Vec<FieldElement> = vec![
+            FieldElement::TWO,
+            FieldElement::ONE,
+            FieldElement::ONE,
+            FieldElement::TWO,
+            FieldElement::ONE,
+            FieldElement::from(256_u32),
+        ];
+
+        match Vec::<Vec<u8>>::deserialize(&felts, 0).unwrap_err() {
+            Error::Context {
+                type_name,
+                path,
+                offset,
+                ..
+            } => {
+                assert_eq!(type_name, "Vec");
+                assert_eq!(path, "[1][1]");
+                assert_eq!(offset, 5);
+            }
+            e => panic!("Unexpected error {:?}", e),
+        }
+    }
+
+    #[test]
+    fn test_serialize_to_array() {
+        let v: Vec<Option<u32>> = vec![Some(1), None];
+        let mut out = vec![FieldElement::TWO];
+        Vec::<Option<u32>>::serialize_to(&v, &mut out);
+        assert_eq!(
+            out,
+            vec![
+                FieldElement::TWO,
+                FieldElement::TWO,
+                FieldElement::ZERO,
+                FieldElement::ONE,
+                FieldElement::ONE,
+            ]
+        );
+        assert_eq!(Vec::<Option<u32>>::serialize(&v), out[1..]);
+    }
+
+    #[test]
+    fn test_deserialize_array_with_size() {
+        let felts: Vec<FieldElement> = vec![
+            FieldElement::TWO,
+            FieldElement::ONE,
+            FieldElement::ONE,
+            FieldElement::TWO,
+            FieldElement::ONE,
+            FieldElement::TWO,
+            FieldElement::THREE,
+        ];
+
+        let (vals, size) = Vec::<Vec<u8>>::deserialize_with_size(&felts, 0).unwrap();
+        assert_eq!(vals, vec![vec![1], vec![1, 2]]);
+        assert_eq!(size, 6);
+    }
+
+    #[test]
+    fn test_deserialize_array_option() {
+        let felts: Vec<FieldElement> = vec![
+            FieldElement::TWO,
+            FieldElement::ZERO,
+            FieldElement::from(12_u32),
+            FieldElement::ONE,
+        ];
+
+        let vals = Vec::<Option<u32>>::deserialize(&felts, 0).unwrap();
+        assert_eq!(vals, vec![Some(12), None]);
+    }
+
     #[test]
     fn test_serialize_array_nested() {
         let v: Vec<Vec<u32>> = vec![vec![1, 2], vec![3]];
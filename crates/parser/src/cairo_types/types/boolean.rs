@@ -1,5 +1,5 @@
 //! CairoType implementation for bool.
-use crate::cairo_types::{CairoType, Result};
+use crate::cairo_types::{get_felt, CairoType, Result};
 use starknet::core::types::FieldElement;
 
 impl CairoType for bool {
@@ -9,12 +9,16 @@ impl CairoType for bool {
         vec![FieldElement::from(*rust as u32)]
     }
 
-    fn deserialize(felts: &[FieldElement], offset: usize) -> Result<Self::RustType> {
-        if felts[offset] == FieldElement::ONE {
-            Ok(true)
-        } else {
-            Ok(false)
-        }
+    #[inline]
+    fn serialize_to(rust: &Self::RustType, out: &mut Vec<FieldElement>) {
+        out.push(FieldElement::from(*rust as u32));
+    }
+
+    fn deserialize_with_size(
+        felts: &[FieldElement],
+        offset: usize,
+    ) -> Result<(Self::RustType, usize)> {
+        Ok((get_felt(felts, offset)? == FieldElement::ONE, 1))
     }
 }
 
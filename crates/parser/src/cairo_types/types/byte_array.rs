@@ -0,0 +1,249 @@
+//! CairoType implementation for `ByteArray`.
+//!
+//! A `ByteArray` is serialized as the array of full 31-bytes words,
+//! followed by the pending word (the remaining bytes) and
+//! the pending word length.
+//!
+//! `String` also implements `CairoType` using the `ByteArray`
+//! serialization, as most of the time a `ByteArray` is an UTF-8 string.
+//!
+//! https://github.com/starkware-libs/cairo/blob/main/corelib/src/byte_array.cairo
+use crate::cairo_types::types::bytes_31::{Bytes31, BYTES31_LEN};
+use crate::cairo_types::{CairoType, Error, Result};
+use starknet::core::types::FieldElement;
+
+/// ByteArray, stored as raw bytes.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct ByteArray(pub Vec<u8>);
+
+impl From<Vec<u8>> for ByteArray {
+    fn from(item: Vec<u8>) -> Self {
+        Self(item)
+    }
+}
+
+impl From<&str> for ByteArray {
+    fn from(item: &str) -> Self {
+        Self(item.as_bytes().to_vec())
+    }
+}
+
+impl From<String> for ByteArray {
+    fn from(item: String) -> Self {
+        Self(item.into_bytes())
+    }
+}
+
+impl TryFrom<ByteArray> for String {
+    type Error = Error;
+
+    fn try_from(item: ByteArray) -> Result<Self> {
+        String::from_utf8(item.0).map_err(|e| Error::Parse(format!("Invalid UTF-8 string: {}", e)))
+    }
+}
+
+/// Serializes raw bytes as a `ByteArray`, appending the felts to `out`.
+fn serialize_bytes_to(bytes: &[u8], out: &mut Vec<FieldElement>) {
+    let chunks = bytes.chunks_exact(BYTES31_LEN);
+    let pending = chunks.remainder();
+
+    out.push(chunks.len().into());
+
+    for c in chunks {
+        // Can't fail as chunks are exactly 31 bytes long.
+        out.push(Bytes31::from_bytes_be(c).unwrap().into());
+    }
+
+    out.push(Bytes31::from_bytes_be(pending).unwrap().into());
+    out.push(pending.len().into());
+}
+
+impl CairoType for ByteArray {
+    type RustType = Self;
+
+    const SERIALIZED_SIZE: Option<usize> = None;
+
+    #[inline]
+    fn serialized_size(rust: &Self::RustType) -> usize {
+        // Array length + full words + pending word + pending word length.
+        1 + rust.0.len() / BYTES31_LEN + 2
+    }
+
+    fn serialize(rust: &Self::RustType) -> Vec<FieldElement> {
+        let mut out = Vec::with_capacity(Self::serialized_size(rust));
+        Self::serialize_to(rust, &mut out);
+        out
+    }
+
+    fn serialize_to(rust: &Self::RustType, out: &mut Vec<FieldElement>) {
+        serialize_bytes_to(&rust.0, out);
+    }
+
+    fn deserialize_with_size(
+        felts: &[FieldElement],
+        offset: usize,
+    ) -> Result<(Self::RustType, usize)> {
+        let (words, size) = Vec::<Bytes31>::deserialize_with_size(felts, offset)?;
+        let mut offset = offset + size;
+
+        let pending_word = Bytes31::deserialize(felts, offset)?;
+        offset += 1;
+
+        let pending_len = usize::deserialize(felts, offset)?;
+        if pending_len >= BYTES31_LEN {
+            return Err(Error::Deserialize(format!(
+                "ByteArray pending word length must be less than {}, got {}",
+                BYTES31_LEN, pending_len
+            )));
+        }
+
+        // The pending word bytes are right aligned, all the bytes
+        // before them are expected to be zero.
+        let (padding, pending) = pending_word.as_bytes().split_at(BYTES31_LEN - pending_len);
+        if padding.iter().any(|b| *b != 0) {
+            return Err(Error::Deserialize(format!(
+                "ByteArray pending word {:#x} doesn't fit in {} byte(s)",
+                FieldElement::from(pending_word),
+                pending_len
+            )));
+        }
+
+        let mut out = Vec::with_capacity(words.len() * BYTES31_LEN + pending_len);
+        for w in &words {
+            out.extend_from_slice(w.as_bytes());
+        }
+        out.extend_from_slice(pending);
+
+        // Full words + pending word + pending word length.
+        Ok((ByteArray(out), size + 2))
+    }
+}
+
+impl CairoType for String {
+    type RustType = Self;
+
+    const SERIALIZED_SIZE: Option<usize> = None;
+
+    #[inline]
+    fn serialized_size(rust: &Self::RustType) -> usize {
+        // Array length + full words + pending word + pending word length.
+        1 + rust.len() / BYTES31_LEN + 2
+    }
+
+    fn serialize(rust: &Self::RustType) -> Vec<FieldElement> {
+        let mut out = Vec::with_capacity(Self::serialized_size(rust));
+        Self::serialize_to(rust, &mut out);
+        out
+    }
+
+    fn serialize_to(rust: &Self::RustType, out: &mut Vec<FieldElement>) {
+        serialize_bytes_to(rust.as_bytes(), out);
+    }
+
+    fn deserialize_with_size(
+        felts: &[FieldElement],
+        offset: usize,
+    ) -> Result<(Self::RustType, usize)> {
+        let (b, size) = ByteArray::deserialize_with_size(felts, offset)?;
+        let s = String::try_from(b).map_err(|e| Error::Deserialize(e.to_string()))?;
+        Ok((s, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_byte_array_serialize_empty() {
+        let felts = String::serialize(&String::new());
+        assert_eq!(
+            felts,
+            vec![FieldElement::ZERO, FieldElement::ZERO, FieldElement::ZERO]
+        );
+    }
+
+    #[test]
+    fn test_byte_array_serialize_pending_only() {
+        let s = "ABC".to_string();
+        let felts = String::serialize(&s);
+        assert_eq!(
+            felts,
+            vec![
+                FieldElement::ZERO,
+                FieldElement::from(0x414243_u32),
+                FieldElement::THREE
+            ]
+        );
+        assert_eq!(String::serialized_size(&s), 3);
+    }
+
+    #[test]
+    fn test_byte_array_serialize_full_word() {
+        let s = "ABCDEFGHIJKLMNOPQRSTUVWXYZ12345".to_string();
+        let felts = String::serialize(&s);
+        assert_eq!(felts.len(), 4);
+        assert_eq!(felts[0], FieldElement::ONE);
+        assert_eq!(
+            felts[1],
+            FieldElement::from_byte_slice_be(s.as_bytes()).unwrap()
+        );
+        assert_eq!(felts[2], FieldElement::ZERO);
+        assert_eq!(felts[3], FieldElement::ZERO);
+        assert_eq!(String::serialized_size(&s), 4);
+    }
+
+    #[test]
+    fn test_byte_array_deserialize() {
+        let s = "ABCDEFGHIJKLMNOPQRSTUVWXYZ12345678".to_string();
+        let mut felts = vec![FieldElement::TWO];
+        felts.extend(String::serialize(&s));
+        felts.push(FieldElement::TWO);
+
+        assert_eq!(String::deserialize(&felts, 1).unwrap(), s);
+        assert_eq!(
+            String::deserialize_with_size(&felts, 1).unwrap().1,
+            String::serialized_size(&s)
+        );
+    }
+
+    #[test]
+    fn test_byte_array_deserialize_bytes() {
+        let b = ByteArray(vec![0xff, 0x00, 0x01]);
+        let felts = ByteArray::serialize(&b);
+        assert_eq!(ByteArray::deserialize(&felts, 0).unwrap(), b);
+        assert!(String::deserialize(&felts, 0).is_err());
+    }
+
+    #[test]
+    fn test_byte_array_deserialize_invalid_pending_len() {
+        let felts = vec![
+            FieldElement::ZERO,
+            FieldElement::ONE,
+            FieldElement::from(31_u32),
+        ];
+        assert!(ByteArray::deserialize(&felts, 0).is_err());
+
+        let felts = vec![
+            FieldElement::ZERO,
+            FieldElement::ONE,
+            FieldElement::from(100_u32),
+        ];
+        assert!(ByteArray::deserialize(&felts, 0).is_err());
+    }
+
+    #[test]
+    fn test_byte_array_deserialize_pending_word_overflow() {
+        // 3 bytes in the pending word, but a length of 2.
+        let felts = vec![
+            FieldElement::ZERO,
+            FieldElement::from(0x414243_u32),
+            FieldElement::TWO,
+        ];
+        assert!(ByteArray::deserialize(&felts, 0).is_err());
+
+        // Any byte is dropped with a length of 0.
+        let felts = vec![FieldElement::ZERO, FieldElement::ONE, FieldElement::ZERO];
+        assert!(ByteArray::deserialize(&felts, 0).is_err());
+    }
+}
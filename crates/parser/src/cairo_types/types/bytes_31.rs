@@ -0,0 +1,128 @@
+//! CairoType implementation for `bytes31`.
+//!
+//! A `bytes31` is a felt that is guaranteed to fit into 31 bytes.
+//!
+//! https://github.com/starkware-libs/cairo/blob/main/corelib/src/bytes_31.cairo
+use crate::cairo_types::{get_felt, CairoType, Error, Result};
+use starknet::core::types::FieldElement;
+
+/// Number of bytes in a `bytes31`.
+pub const BYTES31_LEN: usize = 31;
+
+/// Bytes31, stored as big-endian bytes.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Bytes31([u8; BYTES31_LEN]);
+
+impl Bytes31 {
+    /// Initializes a new instance from big-endian bytes.
+    /// The bytes are left padded with zeros if less than 31 bytes are given.
+    pub fn from_bytes_be(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() > BYTES31_LEN {
+            return Err(Error::Parse(format!(
+                "bytes31 expects at most {} bytes, got {}",
+                BYTES31_LEN,
+                bytes.len()
+            )));
+        }
+
+        let mut out = [0_u8; BYTES31_LEN];
+        out[BYTES31_LEN - bytes.len()..].copy_from_slice(bytes);
+        Ok(Self(out))
+    }
+
+    /// Returns the big-endian bytes.
+    pub fn as_bytes(&self) -> &[u8; BYTES31_LEN] {
+        &self.0
+    }
+}
+
+impl From<[u8; BYTES31_LEN]> for Bytes31 {
+    fn from(item: [u8; BYTES31_LEN]) -> Self {
+        Self(item)
+    }
+}
+
+impl TryFrom<FieldElement> for Bytes31 {
+    type Error = Error;
+
+    fn try_from(item: FieldElement) -> Result<Self> {
+        let bytes = item.to_bytes_be();
+
+        if bytes[0] != 0 {
+            return Err(Error::Parse(format!(
+                "Felt {:#x} doesn't fit into bytes31",
+                item
+            )));
+        }
+
+        Self::from_bytes_be(&bytes[1..])
+    }
+}
+
+impl From<Bytes31> for FieldElement {
+    fn from(item: Bytes31) -> Self {
+        // Can't fail as 31 bytes always fit into a felt.
+        FieldElement::from_byte_slice_be(&item.0).unwrap()
+    }
+}
+
+impl CairoType for Bytes31 {
+    type RustType = Self;
+
+    fn serialize(rust: &Self::RustType) -> Vec<FieldElement> {
+        vec![FieldElement::from(*rust)]
+    }
+
+    #[inline]
+    fn serialize_to(rust: &Self::RustType, out: &mut Vec<FieldElement>) {
+        out.push(FieldElement::from(*rust));
+    }
+
+    fn deserialize_with_size(
+        felts: &[FieldElement],
+        offset: usize,
+    ) -> Result<(Self::RustType, usize)> {
+        let b = Bytes31::try_from(get_felt(felts, offset)?)
+            .map_err(|e| Error::Deserialize(e.to_string()))?;
+        Ok((b, 1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bytes31_from_bytes_be() {
+        let b = Bytes31::from_bytes_be(&[1, 2]).unwrap();
+        assert_eq!(b.as_bytes()[29], 1);
+        assert_eq!(b.as_bytes()[30], 2);
+        assert_eq!(b.as_bytes()[..29], [0_u8; 29]);
+
+        assert!(Bytes31::from_bytes_be(&[0_u8; 32]).is_err());
+    }
+
+    #[test]
+    fn test_bytes31_serialize() {
+        let b = Bytes31::from_bytes_be(&[1, 2]).unwrap();
+        let felts = Bytes31::serialize(&b);
+        assert_eq!(felts.len(), 1);
+        assert_eq!(felts[0], FieldElement::from(0x0102_u32));
+    }
+
+    #[test]
+    fn test_bytes31_deserialize() {
+        let felts = vec![FieldElement::from(0x0102_u32)];
+        let b = Bytes31::deserialize(&felts, 0).unwrap();
+        assert_eq!(b, Bytes31::from_bytes_be(&[1, 2]).unwrap());
+    }
+
+    #[test]
+    fn test_bytes31_deserialize_too_large() {
+        let felts = vec![FieldElement::from_hex_be(
+            "0x0100000000000000000000000000000000000000000000000000000000000000",
+        )
+        .unwrap()];
+        assert!(Bytes31::deserialize(&felts, 0).is_err());
+    }
+}
@@ -1,15 +1,23 @@
-use crate::cairo_types::{CairoType, Result};
+use crate::cairo_types::{get_felt, CairoType, Result};
 use starknet::core::types::FieldElement;
 
 impl CairoType for () {
     type RustType = Self;
 
+    const SERIALIZED_SIZE: Option<usize> = Some(0);
+
     fn serialize(_rust: &Self::RustType) -> Vec<FieldElement> {
         vec![]
     }
 
-    fn deserialize(_felts: &[FieldElement], _offset: usize) -> Result<Self::RustType> {
-        Ok(())
+    #[inline]
+    fn serialize_to(_rust: &Self::RustType, _out: &mut Vec<FieldElement>) {}
+
+    fn deserialize_with_size(
+        _felts: &[FieldElement],
+        _offset: usize,
+    ) -> Result<(Self::RustType, usize)> {
+        Ok(((), 0))
     }
 }
 
@@ -20,14 +28,23 @@ impl CairoType for FieldElement {
         vec![*rust]
     }
 
-    fn deserialize(felts: &[FieldElement], offset: usize) -> Result<Self::RustType> {
-        Ok(felts[offset])
+    #[inline]
+    fn serialize_to(rust: &Self::RustType, out: &mut Vec<FieldElement>) {
+        out.push(*rust);
+    }
+
+    fn deserialize_with_size(
+        felts: &[FieldElement],
+        offset: usize,
+    ) -> Result<(Self::RustType, usize)> {
+        Ok((get_felt(felts, offset)?, 1))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cairo_types::Error;
 
     #[test]
     fn test_serialize_field_element() {
@@ -53,4 +70,17 @@ mod tests {
             FieldElement::TWO
         );
     }
+
+    #[test]
+    fn test_deserialize_field_element_out_of_felts() {
+        let felts = vec![FieldElement::ZERO];
+        assert_eq!(
+            FieldElement::deserialize(&felts, 1).unwrap_err(),
+            Error::OutOfFelts {
+                expected: 1,
+                available: 0,
+                offset: 1
+            }
+        );
+    }
 }
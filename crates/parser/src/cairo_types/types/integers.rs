@@ -1,5 +1,8 @@
 //! CairoType implementation for integers (signed/unsigned).
-use crate::cairo_types::{CairoType, Result};
+//!
+//! Signed integers are represented in cairo with the field
+//! prime wraparound: a negative value `x` is serialized as `P - |x|`.
+use crate::cairo_types::{get_felt, CairoType, Error, Result};
 use starknet::core::types::FieldElement;
 
 macro_rules! implement_trait_for_unsigned {
@@ -11,9 +14,27 @@ macro_rules! implement_trait_for_unsigned {
                 vec![FieldElement::from(*rust)]
             }
 
-            fn deserialize(felts: &[FieldElement], offset: usize) -> Result<Self::RustType> {
-                let temp: u128 = felts[offset].try_into().unwrap();
-                Ok(temp as $type)
+            #[inline]
+            fn serialize_to(rust: &Self::RustType, out: &mut Vec<FieldElement>) {
+                out.push(FieldElement::from(*rust));
+            }
+
+            fn deserialize_with_size(
+                felts: &[FieldElement],
+                offset: usize,
+            ) -> Result<(Self::RustType, usize)> {
+                let felt = get_felt(felts, offset)?;
+                let out_of_range = || {
+                    Error::Deserialize(format!(
+                        "Felt {:#x} is out of range for {}",
+                        felt,
+                        stringify!($type)
+                    ))
+                };
+
+                let v: u128 = felt.try_into().map_err(|_| out_of_range())?;
+                let v = <$type>::try_from(v).map_err(|_| out_of_range())?;
+                Ok((v, 1))
             }
         }
     };
@@ -25,12 +46,53 @@ macro_rules! implement_trait_for_signed {
             type RustType = Self;
 
             fn serialize(rust: &Self::RustType) -> Vec<FieldElement> {
-                vec![FieldElement::from(*rust as usize)]
+                let mut out = Vec::with_capacity(1);
+                Self::serialize_to(rust, &mut out);
+                out
             }
 
-            fn deserialize(felts: &[FieldElement], offset: usize) -> Result<Self::RustType> {
-                let temp: u128 = felts[offset].try_into().unwrap();
-                Ok(temp as $type)
+            #[inline]
+            fn serialize_to(rust: &Self::RustType, out: &mut Vec<FieldElement>) {
+                let abs = FieldElement::from(rust.unsigned_abs());
+
+                if *rust < 0 {
+                    out.push(FieldElement::ZERO - abs);
+                } else {
+                    out.push(abs);
+                }
+            }
+
+            fn deserialize_with_size(
+                felts: &[FieldElement],
+                offset: usize,
+            ) -> Result<(Self::RustType, usize)> {
+                let felt = get_felt(felts, offset)?;
+                let out_of_range = || {
+                    Error::Deserialize(format!(
+                        "Felt {:#x} is out of range for {}",
+                        felt,
+                        stringify!($type)
+                    ))
+                };
+
+                if let Ok(v) = u128::try_from(felt) {
+                    if v <= <$type>::MAX as u128 {
+                        return Ok((v as $type, 1));
+                    }
+                }
+
+                // Not a positive value, the felt is expected to be `P - |x|`.
+                let abs: u128 = (FieldElement::ZERO - felt)
+                    .try_into()
+                    .map_err(|_| out_of_range())?;
+
+                if abs <= <$type>::MIN.unsigned_abs() as u128 {
+                    // Wrapping is required for `MIN`, which absolute value
+                    // doesn't fit into the signed type.
+                    Ok(((abs as $type).wrapping_neg(), 1))
+                } else {
+                    Err(out_of_range())
+                }
             }
         }
     };
@@ -224,4 +286,111 @@ mod tests {
         assert_eq!(i128::deserialize(&felts, 0).unwrap(), i128::MAX);
         assert_eq!(i128::deserialize(&felts, 1).unwrap(), i128::MAX);
     }
+
+    #[test]
+    fn test_deserialize_unsigned_out_of_range() {
+        let felts = vec![
+            FieldElement::from(300_u32),
+            FieldElement::from(u16::MAX as u32 + 1),
+            FieldElement::from(u32::MAX as u64 + 1),
+            FieldElement::from(u64::MAX as u128 + 1),
+            FieldElement::from(u128::MAX) + FieldElement::ONE,
+        ];
+
+        let e = u8::deserialize(&felts, 0).unwrap_err();
+        assert_eq!(
+            e,
+            Error::Deserialize("Felt 0x12c is out of range for u8".to_string())
+        );
+        assert!(u16::deserialize(&felts, 1).is_err());
+        assert!(u32::deserialize(&felts, 2).is_err());
+        assert!(u64::deserialize(&felts, 3).is_err());
+        assert!(u128::deserialize(&felts, 4).is_err());
+        assert!(usize::deserialize(&felts, 4).is_err());
+    }
+
+    #[test]
+    fn test_deserialize_unsigned_max() {
+        let felts = vec![
+            FieldElement::from(u8::MAX),
+            FieldElement::from(u16::MAX),
+            FieldElement::from(u32::MAX),
+            FieldElement::from(u64::MAX),
+            FieldElement::from(u128::MAX),
+        ];
+        assert_eq!(u8::deserialize(&felts, 0).unwrap(), u8::MAX);
+        assert_eq!(u16::deserialize(&felts, 1).unwrap(), u16::MAX);
+        assert_eq!(u32::deserialize(&felts, 2).unwrap(), u32::MAX);
+        assert_eq!(u64::deserialize(&felts, 3).unwrap(), u64::MAX);
+        assert_eq!(u128::deserialize(&felts, 4).unwrap(), u128::MAX);
+    }
+
+    fn assert_signed_round_trip<T>(values: &[T])
+    where
+        T: CairoType<RustType = T> + PartialEq + std::fmt::Debug,
+    {
+        for v in values {
+            let felts = T::serialize(v);
+            assert_eq!(felts.len(), 1);
+            assert_eq!(&T::deserialize(&felts, 0).unwrap(), v);
+        }
+    }
+
+    #[test]
+    fn test_serialize_signed_negative() {
+        let p_minus_one = FieldElement::ZERO - FieldElement::ONE;
+
+        assert_eq!(i8::serialize(&-1), vec![p_minus_one]);
+        assert_eq!(i16::serialize(&-1), vec![p_minus_one]);
+        assert_eq!(i32::serialize(&-1), vec![p_minus_one]);
+        assert_eq!(i64::serialize(&-1), vec![p_minus_one]);
+        assert_eq!(i128::serialize(&-1), vec![p_minus_one]);
+
+        assert_eq!(
+            i8::serialize(&i8::MIN),
+            vec![FieldElement::ZERO - FieldElement::from(128_u8)]
+        );
+        assert_eq!(
+            i128::serialize(&i128::MIN),
+            vec![FieldElement::ZERO - FieldElement::from(i128::MIN.unsigned_abs())]
+        );
+    }
+
+    #[test]
+    fn test_deserialize_signed_negative() {
+        let felts = vec![FieldElement::ZERO - FieldElement::ONE];
+        assert_eq!(i8::deserialize(&felts, 0).unwrap(), -1);
+        assert_eq!(i16::deserialize(&felts, 0).unwrap(), -1);
+        assert_eq!(i32::deserialize(&felts, 0).unwrap(), -1);
+        assert_eq!(i64::deserialize(&felts, 0).unwrap(), -1);
+        assert_eq!(i128::deserialize(&felts, 0).unwrap(), -1);
+    }
+
+    #[test]
+    fn test_signed_round_trip_boundaries() {
+        assert_signed_round_trip(&[i8::MIN, i8::MIN + 1, -1, 0, 1, i8::MAX - 1, i8::MAX]);
+        assert_signed_round_trip(&[i16::MIN, i16::MIN + 1, -1, 0, 1, i16::MAX - 1, i16::MAX]);
+        assert_signed_round_trip(&[i32::MIN, i32::MIN + 1, -1, 0, 1, i32::MAX - 1, i32::MAX]);
+        assert_signed_round_trip(&[i64::MIN, i64::MIN + 1, -1, 0, 1, i64::MAX - 1, i64::MAX]);
+        assert_signed_round_trip(&[i128::MIN, i128::MIN + 1, -1, 0, 1, i128::MAX - 1, i128::MAX]);
+    }
+
+    #[test]
+    fn test_deserialize_signed_out_of_range() {
+        let felts = vec![
+            FieldElement::from(128_u32),
+            FieldElement::ZERO - FieldElement::from(129_u32),
+        ];
+        assert!(i8::deserialize(&felts, 0).is_err());
+        assert!(i8::deserialize(&felts, 1).is_err());
+
+        let felts = vec![
+            FieldElement::from(i128::MAX as u128 + 1),
+            FieldElement::ZERO - FieldElement::from(i128::MAX as u128 + 2),
+            FieldElement::from(u128::MAX) + FieldElement::ONE,
+        ];
+        assert!(i128::deserialize(&felts, 0).is_err());
+        assert!(i128::deserialize(&felts, 1).is_err());
+        assert!(i128::deserialize(&felts, 2).is_err());
+    }
 }
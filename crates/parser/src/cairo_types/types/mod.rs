@@ -1,8 +1,11 @@
 pub mod array;
 pub mod boolean;
+pub mod byte_array;
+pub mod bytes_31;
 pub mod felt;
 pub mod integers;
 pub mod option;
 pub mod result;
 pub mod starknet;
 pub mod tuple;
+pub mod u256;
@@ -4,7 +4,7 @@
 //! To follow the serialization rule, `Some` has index 0, and `None` index 1.
 //!
 //! https://github.com/starkware-libs/cairo/blob/main/corelib/src/option.cairo#L6
-use crate::cairo_types::{CairoType, Error, Result};
+use crate::cairo_types::{get_felt, CairoType, Error, Result};
 use starknet::core::types::FieldElement;
 
 impl<T, RT> CairoType for Option<T>
@@ -13,28 +13,44 @@ where
 {
     type RustType = Option<RT>;
 
+    const SERIALIZED_SIZE: Option<usize> = None;
+
+    #[inline]
+    fn serialized_size(rust: &Self::RustType) -> usize {
+        match rust {
+            Some(r) => 1 + T::serialized_size(r),
+            None => 1,
+        }
+    }
+
     fn serialize(rust: &Self::RustType) -> Vec<FieldElement> {
-        let mut out = vec![];
+        let mut out = Vec::with_capacity(Self::serialized_size(rust));
+        Self::serialize_to(rust, &mut out);
+        out
+    }
 
+    fn serialize_to(rust: &Self::RustType, out: &mut Vec<FieldElement>) {
         match rust {
             Some(r) => {
                 out.push(FieldElement::ZERO);
-                out.extend(T::serialize(r));
+                T::serialize_to(r, out);
             }
             None => out.push(FieldElement::ONE),
         };
-
-        out
     }
 
-    fn deserialize(felts: &[FieldElement], offset: usize) -> Result<Self::RustType> {
-        let idx = felts[offset];
+    fn deserialize_with_size(
+        felts: &[FieldElement],
+        offset: usize,
+    ) -> Result<(Self::RustType, usize)> {
+        let idx = get_felt(felts, offset)?;
 
         if idx == FieldElement::ZERO {
             // + 1 as the offset value is the index of the enum.
-            Ok(Option::Some(T::deserialize(felts, offset + 1)?))
+            let (r, size) = T::deserialize_with_size(felts, offset + 1)?;
+            Ok((Option::Some(r), 1 + size))
         } else if idx == FieldElement::ONE {
-            Ok(Option::None)
+            Ok((Option::None, 1))
         } else {
             Err(Error::Deserialize(
                 "Option is expected 0 or 1 index only".to_string(),
@@ -57,6 +73,21 @@ mod tests {
         assert_eq!(felts[1], FieldElement::from(u32::MAX));
     }
 
+    #[test]
+    fn test_option_deserialize_with_size() {
+        let felts = vec![FieldElement::ZERO, FieldElement::from(8_u32)];
+        assert_eq!(
+            Option::<u32>::deserialize_with_size(&felts, 0).unwrap(),
+            (Some(8), 2)
+        );
+
+        let felts = vec![FieldElement::ONE];
+        assert_eq!(
+            Option::<u32>::deserialize_with_size(&felts, 0).unwrap(),
+            (None, 1)
+        );
+    }
+
     #[test]
     fn test_option_some_deserialize() {
         let felts = vec![FieldElement::ZERO, FieldElement::from(u32::MAX)];
@@ -105,6 +136,19 @@ mod tests {
         assert_eq!(o, Some(vec![u32::MAX, u32::MAX]));
     }
 
+    #[test]
+    fn test_option_out_of_felts() {
+        let felts = vec![FieldElement::ZERO];
+        assert!(Option::<u32>::deserialize(&felts, 0).is_err());
+        assert!(Option::<u32>::deserialize(&felts, 1).is_err());
+    }
+
+    #[test]
+    fn test_option_serialized_size() {
+        assert_eq!(Option::<u32>::serialized_size(&Some(1)), 2);
+        assert_eq!(Option::<u32>::serialized_size(&None), 1);
+    }
+
     #[test]
     fn test_option_none_serialize() {
         let o: Option<u32> = None;
@@ -1,7 +1,7 @@
 //! CairoType implementation for Result.
 //!
 //! https://github.com/starkware-libs/cairo/blob/main/corelib/src/result.cairo#L6
-use crate::cairo_types::{CairoType, Error as CairoError, Result as CairoResult};
+use crate::cairo_types::{get_felt, CairoType, Error as CairoError, Result as CairoResult};
 use starknet::core::types::FieldElement;
 
 impl<T, RT, E, RE> CairoType for Result<T, E>
@@ -11,31 +11,48 @@ where
 {
     type RustType = Result<RT, RE>;
 
+    const SERIALIZED_SIZE: Option<usize> = None;
+
+    #[inline]
+    fn serialized_size(rust: &Self::RustType) -> usize {
+        match rust {
+            Ok(r) => 1 + T::serialized_size(r),
+            Err(e) => 1 + E::serialized_size(e),
+        }
+    }
+
     fn serialize(rust: &Self::RustType) -> Vec<FieldElement> {
-        let mut out = vec![];
+        let mut out = Vec::with_capacity(Self::serialized_size(rust));
+        Self::serialize_to(rust, &mut out);
+        out
+    }
 
+    fn serialize_to(rust: &Self::RustType, out: &mut Vec<FieldElement>) {
         match rust {
             Result::Ok(r) => {
                 out.push(FieldElement::ZERO);
-                out.extend(T::serialize(r));
+                T::serialize_to(r, out);
             }
             Result::Err(e) => {
                 out.push(FieldElement::ONE);
-                out.extend(E::serialize(e));
+                E::serialize_to(e, out);
             }
         };
-
-        out
     }
 
-    fn deserialize(felts: &[FieldElement], offset: usize) -> CairoResult<Self::RustType> {
-        let idx = felts[offset];
+    fn deserialize_with_size(
+        felts: &[FieldElement],
+        offset: usize,
+    ) -> CairoResult<(Self::RustType, usize)> {
+        let idx = get_felt(felts, offset)?;
 
         if idx == FieldElement::ZERO {
             // + 1 as the offset value is the index of the enum.
-            CairoResult::Ok(Ok(T::deserialize(felts, offset + 1)?))
+            let (r, size) = T::deserialize_with_size(felts, offset + 1)?;
+            CairoResult::Ok((Ok(r), 1 + size))
         } else if idx == FieldElement::ONE {
-            CairoResult::Ok(Err(E::deserialize(felts, offset + 1)?))
+            let (e, size) = E::deserialize_with_size(felts, offset + 1)?;
+            CairoResult::Ok((Err(e), 1 + size))
         } else {
             Err(CairoError::Deserialize(
                 "Result is expected 0 or 1 index only".to_string(),
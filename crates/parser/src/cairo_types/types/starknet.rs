@@ -27,8 +27,19 @@ impl CairoType for ContractAddress {
         FieldElement::serialize(&rust.0)
     }
 
-    fn deserialize(felts: &[FieldElement], offset: usize) -> Result<Self::RustType> {
-        Ok(ContractAddress(FieldElement::deserialize(felts, offset)?))
+    #[inline]
+    fn serialize_to(rust: &Self::RustType, out: &mut Vec<FieldElement>) {
+        out.push(rust.0);
+    }
+
+    fn deserialize_with_size(
+        felts: &[FieldElement],
+        offset: usize,
+    ) -> Result<(Self::RustType, usize)> {
+        Ok((
+            ContractAddress(FieldElement::deserialize(felts, offset)?),
+            1,
+        ))
     }
 }
 
@@ -55,8 +66,16 @@ impl CairoType for ClassHash {
         FieldElement::serialize(&rust.0)
     }
 
-    fn deserialize(felts: &[FieldElement], offset: usize) -> Result<Self::RustType> {
-        Ok(ClassHash(FieldElement::deserialize(felts, offset)?))
+    #[inline]
+    fn serialize_to(rust: &Self::RustType, out: &mut Vec<FieldElement>) {
+        out.push(rust.0);
+    }
+
+    fn deserialize_with_size(
+        felts: &[FieldElement],
+        offset: usize,
+    ) -> Result<(Self::RustType, usize)> {
+        Ok((ClassHash(FieldElement::deserialize(felts, offset)?), 1))
     }
 }
 
@@ -83,8 +102,16 @@ impl CairoType for EthAddress {
         FieldElement::serialize(&rust.0)
     }
 
-    fn deserialize(felts: &[FieldElement], offset: usize) -> Result<Self::RustType> {
-        Ok(EthAddress(FieldElement::deserialize(felts, offset)?))
+    #[inline]
+    fn serialize_to(rust: &Self::RustType, out: &mut Vec<FieldElement>) {
+        out.push(rust.0);
+    }
+
+    fn deserialize_with_size(
+        felts: &[FieldElement],
+        offset: usize,
+    ) -> Result<(Self::RustType, usize)> {
+        Ok((EthAddress(FieldElement::deserialize(felts, offset)?), 1))
     }
 }
 
@@ -23,25 +23,27 @@ macro_rules! impl_tuples {
             }
 
             fn serialize(rust: &Self::RustType) -> Vec<FieldElement> {
-                let mut out: Vec<FieldElement> = vec![];
-
-                $( out.extend($ty::serialize(& rust.$no)); )*
-
+                let mut out = Vec::with_capacity(Self::serialized_size(rust));
+                Self::serialize_to(rust, &mut out);
                 out
             }
 
-            fn deserialize(felts: &[FieldElement], offset: usize) -> Result<Self::RustType> {
-                let mut offset = offset;
+            fn serialize_to(rust: &Self::RustType, out: &mut Vec<FieldElement>) {
+                $( $ty::serialize_to(& rust.$no, out); )*
+            }
+
+            fn deserialize_with_size(
+                felts: &[FieldElement],
+                offset: usize,
+            ) -> Result<(Self::RustType, usize)> {
+                let mut size = 0;
 
                 $(
-                    let $var : $rt = $ty::deserialize(felts, offset)?;
-                    offset += $ty::serialized_size(& $var);
+                    let ($var, s) : ($rt, usize) = $ty::deserialize_with_size(felts, offset + size)?;
+                    size += s;
                 )*
 
-                // Remove warning.
-                let _offset = offset;
-
-                Ok(($( $var ),*))
+                Ok((($( $var ),*), size))
             }
         }
     }
@@ -51,6 +53,13 @@ impl_tuples!(2, A:RA:r0:0, B:RB:r1:1);
 impl_tuples!(3, A:RA:r0:0, B:RB:r1:1, C:RC:r2:2);
 impl_tuples!(4, A:RA:r0:0, B:RB:r1:1, C:RC:r2:2, D:RD:r3:3);
 impl_tuples!(5, A:RA:r0:0, B:RB:r1:1, C:RC:r2:2, D:RD:r3:3, E:RE:r4:4);
+impl_tuples!(6, A:RA:r0:0, B:RB:r1:1, C:RC:r2:2, D:RD:r3:3, E:RE:r4:4, F:RF:r5:5);
+impl_tuples!(7, A:RA:r0:0, B:RB:r1:1, C:RC:r2:2, D:RD:r3:3, E:RE:r4:4, F:RF:r5:5, G:RG:r6:6);
+impl_tuples!(8, A:RA:r0:0, B:RB:r1:1, C:RC:r2:2, D:RD:r3:3, E:RE:r4:4, F:RF:r5:5, G:RG:r6:6, H:RH:r7:7);
+impl_tuples!(9, A:RA:r0:0, B:RB:r1:1, C:RC:r2:2, D:RD:r3:3, E:RE:r4:4, F:RF:r5:5, G:RG:r6:6, H:RH:r7:7, I:RI:r8:8);
+impl_tuples!(10, A:RA:r0:0, B:RB:r1:1, C:RC:r2:2, D:RD:r3:3, E:RE:r4:4, F:RF:r5:5, G:RG:r6:6, H:RH:r7:7, I:RI:r8:8, J:RJ:r9:9);
+impl_tuples!(11, A:RA:r0:0, B:RB:r1:1, C:RC:r2:2, D:RD:r3:3, E:RE:r4:4, F:RF:r5:5, G:RG:r6:6, H:RH:r7:7, I:RI:r8:8, J:RJ:r9:9, K:RK:r10:10);
+impl_tuples!(12, A:RA:r0:0, B:RB:r1:1, C:RC:r2:2, D:RD:r3:3, E:RE:r4:4, F:RF:r5:5, G:RG:r6:6, H:RH:r7:7, I:RI:r8:8, J:RJ:r9:9, K:RK:r10:10, L:RL:r11:11);
 
 #[cfg(test)]
 mod tests {
@@ -83,6 +92,21 @@ mod tests {
         assert_eq!(felts[2], FieldElement::from(128_u32));
     }
 
+    #[test]
+    fn test_deserialize_tuple2_with_size() {
+        let felts = vec![
+            FieldElement::ZERO,
+            FieldElement::TWO,
+            FieldElement::ONE,
+            FieldElement::ONE,
+            99_u32.into(),
+        ];
+        let (vals, size) = <(Vec<FieldElement>, u32)>::deserialize_with_size(&felts, 1).unwrap();
+        assert_eq!(vals.0, vec![FieldElement::ONE, FieldElement::ONE]);
+        assert_eq!(vals.1, 99_u32);
+        assert_eq!(size, 4);
+    }
+
     #[test]
     fn test_deserialize_tuple2_array() {
         let felts = vec![FieldElement::ONE, FieldElement::ONE, 99_u32.into()];
@@ -90,4 +114,15 @@ mod tests {
         assert_eq!(vals.0, vec![FieldElement::ONE]);
         assert_eq!(vals.1, 99_u32);
     }
+
+    #[test]
+    fn test_deserialize_tuple6() {
+        let felts: Vec<FieldElement> = (1_u32..=6).map(FieldElement::from).collect();
+        let vals = <(u8, u16, u32, u64, u128, FieldElement)>::deserialize(&felts, 0).unwrap();
+        assert_eq!(vals, (1, 2, 3, 4, 5, FieldElement::from(6_u32)));
+        assert_eq!(
+            <(u8, u16, u32, u64, u128, FieldElement)>::serialize(&vals),
+            felts
+        );
+    }
 }
@@ -0,0 +1,476 @@
+//! CairoType implementation for `u256`.
+//!
+//! In cairo, `u256` is a struct of two `u128` words, serialized
+//! with the `low` word first.
+//!
+//! https://github.com/starkware-libs/cairo/blob/main/corelib/src/integer.cairo
+use crate::cairo_types::{check_felts_len, CairoType, Error, Result};
+use starknet::core::types::FieldElement;
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+/// Cairo `u256`, made of two `u128` words.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct U256 {
+    pub low: u128,
+    pub high: u128,
+}
+
+impl U256 {
+    pub const ZERO: U256 = U256::from_words(0, 0);
+    pub const ONE: U256 = U256::from_words(1, 0);
+    pub const MAX: U256 = U256::from_words(u128::MAX, u128::MAX);
+
+    /// Initializes a new instance from the low and high words.
+    pub const fn from_words(low: u128, high: u128) -> Self {
+        U256 { low, high }
+    }
+
+    /// Initializes a new instance from big-endian bytes.
+    pub fn from_bytes_be(bytes: &[u8; 32]) -> Self {
+        let mut high = [0_u8; 16];
+        let mut low = [0_u8; 16];
+        high.copy_from_slice(&bytes[..16]);
+        low.copy_from_slice(&bytes[16..]);
+
+        U256 {
+            low: u128::from_be_bytes(low),
+            high: u128::from_be_bytes(high),
+        }
+    }
+
+    /// Returns the big-endian bytes representation.
+    pub fn to_bytes_be(&self) -> [u8; 32] {
+        let mut bytes = [0_u8; 32];
+        bytes[..16].copy_from_slice(&self.high.to_be_bytes());
+        bytes[16..].copy_from_slice(&self.low.to_be_bytes());
+        bytes
+    }
+
+    /// Parses an hexadecimal string, with or without the `0x` prefix.
+    pub fn from_hex_str(s: &str) -> Result<Self> {
+        let digits = s
+            .strip_prefix("0x")
+            .or_else(|| s.strip_prefix("0X"))
+            .unwrap_or(s);
+
+        if digits.is_empty() || digits.len() > 64 || !digits.chars().all(|c| c.is_ascii_hexdigit())
+        {
+            return Err(Error::Parse(format!(
+                "Invalid u256 hexadecimal string {}",
+                s
+            )));
+        }
+
+        // The low word is always the 32 last digits.
+        let split = digits.len().saturating_sub(32);
+        let (high, low) = digits.split_at(split);
+        let parse_word = |w: &str| {
+            if w.is_empty() {
+                Ok(0)
+            } else {
+                u128::from_str_radix(w, 16).map_err(|e| {
+                    Error::Parse(format!("Invalid u256 hexadecimal string {}: {}", s, e))
+                })
+            }
+        };
+
+        Ok(U256 {
+            low: parse_word(low)?,
+            high: parse_word(high)?,
+        })
+    }
+
+    /// Parses a decimal string.
+    pub fn from_dec_str(s: &str) -> Result<Self> {
+        if s.is_empty() {
+            return Err(Error::Parse("Empty u256 decimal string".to_string()));
+        }
+
+        let mut value = U256::ZERO;
+
+        for c in s.chars() {
+            let digit = c
+                .to_digit(10)
+                .ok_or_else(|| Error::Parse(format!("Invalid u256 decimal string {}", s)))?;
+
+            value = value
+                .mul_add_small(10, digit as u64)
+                .ok_or_else(|| Error::Parse(format!("Decimal string {} overflows u256", s)))?;
+        }
+
+        Ok(value)
+    }
+
+    /// Returns the four `u64` limbs, least significant first.
+    fn to_limbs(self) -> [u64; 4] {
+        [
+            self.low as u64,
+            (self.low >> 64) as u64,
+            self.high as u64,
+            (self.high >> 64) as u64,
+        ]
+    }
+
+    /// Initializes a new instance from four `u64` limbs, least significant first.
+    fn from_limbs(limbs: [u64; 4]) -> Self {
+        U256 {
+            low: (limbs[1] as u128) << 64 | limbs[0] as u128,
+            high: (limbs[3] as u128) << 64 | limbs[2] as u128,
+        }
+    }
+
+    /// Computes `self * m + a`, returning `None` on overflow.
+    fn mul_add_small(self, m: u64, a: u64) -> Option<Self> {
+        let mut limbs = self.to_limbs();
+        let mut carry = a as u128;
+
+        for limb in limbs.iter_mut() {
+            let v = (*limb as u128) * (m as u128) + carry;
+            *limb = v as u64;
+            carry = v >> 64;
+        }
+
+        if carry != 0 {
+            None
+        } else {
+            Some(Self::from_limbs(limbs))
+        }
+    }
+
+    /// Computes `self / d` and `self % d`.
+    fn div_rem_small(self, d: u64) -> (Self, u64) {
+        let mut limbs = self.to_limbs();
+        let mut rem: u128 = 0;
+
+        for limb in limbs.iter_mut().rev() {
+            let cur = rem << 64 | *limb as u128;
+            *limb = (cur / d as u128) as u64;
+            rem = cur % d as u128;
+        }
+
+        (Self::from_limbs(limbs), rem as u64)
+    }
+}
+
+impl Ord for U256 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.high
+            .cmp(&other.high)
+            .then_with(|| self.low.cmp(&other.low))
+    }
+}
+
+impl PartialOrd for U256 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+macro_rules! implement_from_unsigned {
+    ($type:ty) => {
+        impl From<$type> for U256 {
+            fn from(item: $type) -> Self {
+                U256::from_words(item as u128, 0)
+            }
+        }
+    };
+}
+
+implement_from_unsigned!(u8);
+implement_from_unsigned!(u16);
+implement_from_unsigned!(u32);
+implement_from_unsigned!(u64);
+implement_from_unsigned!(u128);
+implement_from_unsigned!(usize);
+
+/// Converts a `(low, high)` pair of words.
+impl From<(u128, u128)> for U256 {
+    fn from(item: (u128, u128)) -> Self {
+        U256::from_words(item.0, item.1)
+    }
+}
+
+/// Converts into a `(low, high)` pair of words.
+impl From<U256> for (u128, u128) {
+    fn from(item: U256) -> Self {
+        (item.low, item.high)
+    }
+}
+
+/// Parses a decimal string, or an hexadecimal string if
+/// prefixed by `0x`.
+impl FromStr for U256 {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if s.starts_with("0x") || s.starts_with("0X") {
+            U256::from_hex_str(s)
+        } else {
+            U256::from_dec_str(s)
+        }
+    }
+}
+
+impl fmt::Display for U256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // 10^19 is the largest power of ten fitting into an `u64`.
+        const CHUNK_DIVISOR: u64 = 10_000_000_000_000_000_000;
+
+        let mut chunks = vec![];
+        let mut value = *self;
+
+        loop {
+            let (q, r) = value.div_rem_small(CHUNK_DIVISOR);
+            chunks.push(r);
+            value = q;
+
+            if value == U256::ZERO {
+                break;
+            }
+        }
+
+        let mut s = chunks.pop().unwrap_or(0).to_string();
+        for c in chunks.iter().rev() {
+            s.push_str(&format!("{:019}", c));
+        }
+
+        f.pad_integral(true, "", &s)
+    }
+}
+
+impl fmt::LowerHex for U256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = if self.high == 0 {
+            format!("{:x}", self.low)
+        } else {
+            format!("{:x}{:032x}", self.high, self.low)
+        };
+
+        f.pad_integral(true, "0x", &s)
+    }
+}
+
+#[cfg(feature = "num-bigint")]
+impl From<U256> for num_bigint::BigUint {
+    fn from(item: U256) -> Self {
+        num_bigint::BigUint::from_bytes_be(&item.to_bytes_be())
+    }
+}
+
+#[cfg(feature = "num-bigint")]
+impl TryFrom<num_bigint::BigUint> for U256 {
+    type Error = Error;
+
+    fn try_from(item: num_bigint::BigUint) -> Result<Self> {
+        let bytes = item.to_bytes_be();
+
+        if bytes.len() > 32 {
+            return Err(Error::Parse(format!("Value {} overflows u256", item)));
+        }
+
+        let mut padded = [0_u8; 32];
+        padded[32 - bytes.len()..].copy_from_slice(&bytes);
+        Ok(U256::from_bytes_be(&padded))
+    }
+}
+
+#[cfg(feature = "primitive-types")]
+impl From<U256> for primitive_types::U256 {
+    fn from(item: U256) -> Self {
+        primitive_types::U256::from_big_endian(&item.to_bytes_be())
+    }
+}
+
+#[cfg(feature = "primitive-types")]
+impl From<primitive_types::U256> for U256 {
+    fn from(item: primitive_types::U256) -> Self {
+        let mut bytes = [0_u8; 32];
+        item.to_big_endian(&mut bytes);
+        U256::from_bytes_be(&bytes)
+    }
+}
+
+impl CairoType for U256 {
+    type RustType = Self;
+
+    const SERIALIZED_SIZE: Option<usize> = Some(2);
+
+    fn serialize(rust: &Self::RustType) -> Vec<FieldElement> {
+        vec![FieldElement::from(rust.low), FieldElement::from(rust.high)]
+    }
+
+    #[inline]
+    fn serialize_to(rust: &Self::RustType, out: &mut Vec<FieldElement>) {
+        out.push(FieldElement::from(rust.low));
+        out.push(FieldElement::from(rust.high));
+    }
+
+    fn deserialize_with_size(
+        felts: &[FieldElement],
+        offset: usize,
+    ) -> Result<(Self::RustType, usize)> {
+        check_felts_len(felts, offset, 2)?;
+
+        let low =
+            u128::deserialize(felts, offset).map_err(|e| e.with_context("U256", ".low", offset))?;
+        let high = u128::deserialize(felts, offset + 1)
+            .map_err(|e| e.with_context("U256", ".high", offset + 1))?;
+        Ok((U256 { low, high }, 2))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_u256_serialize() {
+        let v = U256::from_words(12, 1);
+        let felts = U256::serialize(&v);
+        assert_eq!(felts.len(), 2);
+        assert_eq!(felts[0], FieldElement::from(12_u128));
+        assert_eq!(felts[1], FieldElement::ONE);
+        assert_eq!(U256::serialized_size(&v), 2);
+    }
+
+    #[test]
+    fn test_u256_deserialize() {
+        let felts = vec![
+            FieldElement::THREE,
+            FieldElement::from(u128::MAX),
+            FieldElement::TWO,
+        ];
+        assert_eq!(
+            U256::deserialize(&felts, 1).unwrap(),
+            U256::from_words(u128::MAX, 2)
+        );
+    }
+
+    #[test]
+    fn test_u256_deserialize_out_of_felts() {
+        let felts = vec![FieldElement::THREE];
+        assert_eq!(
+            U256::deserialize(&felts, 0).unwrap_err(),
+            Error::OutOfFelts {
+                expected: 2,
+                available: 1,
+                offset: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_u256_words() {
+        let v = U256::from((1_u128, 2_u128));
+        assert_eq!(v, U256 { low: 1, high: 2 });
+        assert_eq!(<(u128, u128)>::from(v), (1, 2));
+        assert_eq!(U256::from(7_u32), U256::from_words(7, 0));
+    }
+
+    #[test]
+    fn test_u256_bytes_be() {
+        let v = U256::from_words(0x0102, 0xff);
+        let bytes = v.to_bytes_be();
+        assert_eq!(bytes[15], 0xff);
+        assert_eq!(bytes[30], 0x01);
+        assert_eq!(bytes[31], 0x02);
+        assert_eq!(U256::from_bytes_be(&bytes), v);
+    }
+
+    #[test]
+    fn test_u256_hex() {
+        let v = U256::from_hex_str("0x1ffffffffffffffffffffffffffffffff").unwrap();
+        assert_eq!(v, U256::from_words(u128::MAX, 1));
+        assert_eq!(format!("{:#x}", v), "0x1ffffffffffffffffffffffffffffffff");
+        assert_eq!(format!("{:x}", U256::ZERO), "0");
+        assert_eq!(U256::from_hex_str("ff").unwrap(), U256::from(255_u8));
+        assert_eq!(
+            U256::from_hex_str(&format!("{:x}", U256::MAX)).unwrap(),
+            U256::MAX
+        );
+
+        assert!(U256::from_hex_str("0x").is_err());
+        assert!(U256::from_hex_str("0xzz").is_err());
+        assert!(U256::from_hex_str(&format!("0x1{:x}", U256::MAX)).is_err());
+    }
+
+    #[test]
+    fn test_u256_dec() {
+        let max_str =
+            "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+        assert_eq!(U256::from_dec_str(max_str).unwrap(), U256::MAX);
+        assert_eq!(U256::MAX.to_string(), max_str);
+        assert_eq!(U256::ZERO.to_string(), "0");
+        assert_eq!(
+            U256::from_words(0, 1).to_string(),
+            "340282366920938463463374607431768211456"
+        );
+        assert_eq!(
+            U256::from_dec_str("340282366920938463463374607431768211456").unwrap(),
+            U256::from_words(0, 1)
+        );
+
+        assert!(U256::from_dec_str("").is_err());
+        assert!(U256::from_dec_str("12a").is_err());
+        assert!(U256::from_dec_str(
+            "115792089237316195423570985008687907853269984665640564039457584007913129639936"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_u256_deserialize_error_path() {
+        let felts = vec![
+            FieldElement::ONE,
+            FieldElement::from_hex_be("0x100000000000000000000000000000000").unwrap(),
+        ];
+
+        match U256::deserialize(&felts, 0).unwrap_err() {
+            Error::Context {
+                type_name,
+                path,
+                offset,
+                ..
+            } => {
+                assert_eq!(type_name, "U256");
+                assert_eq!(path, ".high");
+                assert_eq!(offset, 1);
+            }
+            e => panic!("Unexpected error {:?}", e),
+        }
+    }
+
+    #[test]
+    fn test_u256_from_str() {
+        assert_eq!("0x10".parse::<U256>().unwrap(), U256::from(16_u8));
+        assert_eq!("10".parse::<U256>().unwrap(), U256::from(10_u8));
+    }
+
+    #[test]
+    fn test_u256_ord() {
+        assert!(U256::from_words(0, 1) > U256::from_words(u128::MAX, 0));
+        assert!(U256::from_words(1, 1) > U256::from_words(0, 1));
+    }
+
+    #[cfg(feature = "num-bigint")]
+    #[test]
+    fn test_u256_biguint() {
+        let b = num_bigint::BigUint::from(U256::MAX);
+        assert_eq!(b.to_string(), U256::MAX.to_string());
+        assert_eq!(U256::try_from(b.clone()).unwrap(), U256::MAX);
+        assert!(U256::try_from(b + 1_u32).is_err());
+    }
+
+    #[cfg(feature = "primitive-types")]
+    #[test]
+    fn test_u256_primitive_types() {
+        let v = U256::from_words(3, 9);
+        let p = primitive_types::U256::from(v);
+        assert_eq!(p.low_u128(), 3);
+        assert_eq!((p >> 128).low_u128(), 9);
+        assert_eq!(U256::from(p), v);
+    }
+}
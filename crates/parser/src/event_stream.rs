@@ -0,0 +1,204 @@
+//! Stream of decoded events, fetched page by page from a provider.
+//!
+//! The generated `Reader` of a contract uses this stream to expose
+//! the events of the contract already decoded into the `Event` enum.
+pub use futures_util::stream::{Stream, StreamExt};
+
+use futures_util::stream;
+use starknet::core::types::{EmittedEvent, EventFilter, FieldElement};
+use starknet::providers::{Provider, ProviderError};
+
+use super::EventError;
+
+/// An error raised while streaming events.
+#[derive(Debug, thiserror::Error)]
+pub enum EventStreamError {
+    #[error("Could not fetch events: {0}")]
+    Provider(#[from] ProviderError),
+    #[error(transparent)]
+    Decode(#[from] EventError),
+}
+
+/// A decoded event, along with the information of the
+/// transaction that emitted it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedEvent<E> {
+    pub event: E,
+    pub from_address: FieldElement,
+    pub block_hash: FieldElement,
+    pub block_number: u64,
+    pub transaction_hash: FieldElement,
+}
+
+impl<E> DecodedEvent<E>
+where
+    E: TryFrom<EmittedEvent, Error = EventError>,
+{
+    /// Decodes the given emitted event.
+    pub fn try_from_emitted_event(event: EmittedEvent) -> Result<Self, EventError> {
+        let from_address = event.from_address;
+        let block_hash = event.block_hash;
+        let block_number = event.block_number;
+        let transaction_hash = event.transaction_hash;
+
+        Ok(DecodedEvent {
+            event: E::try_from(event)?,
+            from_address,
+            block_hash,
+            block_number,
+            transaction_hash,
+        })
+    }
+}
+
+/// Streams the events matching the filter, decoded into `E`.
+///
+/// Events are fetched by pages of `chunk_size` events, following the
+/// continuation token until the last page. A provider error ends the stream,
+/// whereas a decoding error is yielded and the stream continues.
+pub fn event_stream<'a, P, E>(
+    provider: &'a P,
+    filter: EventFilter,
+    chunk_size: u64,
+) -> impl Stream<Item = Result<DecodedEvent<E>, EventStreamError>> + 'a
+where
+    P: Provider + Sync,
+    E: TryFrom<EmittedEvent, Error = EventError> + 'a,
+{
+    // The state is the continuation token of the next page to fetch,
+    // `None` once the last page is reached.
+    let pages = stream::unfold(Some(None), move |token: Option<Option<String>>| {
+        let filter = filter.clone();
+
+        async move {
+            let token = token?;
+
+            match provider.get_events(filter, token, chunk_size).await {
+                Ok(page) => {
+                    let events: Vec<Result<DecodedEvent<E>, EventStreamError>> = page
+                        .events
+                        .into_iter()
+                        .map(|e| DecodedEvent::try_from_emitted_event(e).map_err(Into::into))
+                        .collect();
+
+                    Some((events, page.continuation_token.map(Some)))
+                }
+                Err(e) => Some((vec![Err(e.into())], None)),
+            }
+        }
+    });
+
+    pages.flat_map(stream::iter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+    use starknet::providers::jsonrpc::{
+        JsonRpcClient, JsonRpcMethod, JsonRpcResponse, JsonRpcTransport,
+    };
+
+    /// Serves two pages of events, linked by a continuation token.
+    struct MockTransport;
+
+    fn find_token(v: &Value) -> Option<String> {
+        match v {
+            Value::Object(o) => match o.get("continuation_token") {
+                Some(t) => t.as_str().map(String::from),
+                None => o.values().find_map(find_token),
+            },
+            Value::Array(a) => a.iter().find_map(find_token),
+            _ => None,
+        }
+    }
+
+    fn event_json(selector: u32, value: u32, block_number: u64) -> Value {
+        json!({
+            "from_address": "0x1",
+            "keys": [format!("{:#x}", selector)],
+            "data": [format!("{:#x}", value)],
+            "block_hash": "0x2",
+            "block_number": block_number,
+            "transaction_hash": "0x3",
+        })
+    }
+
+    #[async_trait::async_trait]
+    impl JsonRpcTransport for MockTransport {
+        type Error = serde_json::Error;
+
+        async fn send_request<P, R>(
+            &self,
+            _method: JsonRpcMethod,
+            params: P,
+        ) -> Result<JsonRpcResponse<R>, Self::Error>
+        where
+            P: serde::Serialize + Send + Sync,
+            R: serde::de::DeserializeOwned,
+        {
+            let page = match find_token(&serde_json::to_value(params)?) {
+                None => json!({
+                    "events": [event_json(1, 10, 1), event_json(2, 20, 1)],
+                    "continuation_token": "1",
+                }),
+                Some(_) => json!({
+                    "events": [event_json(1, 30, 2)],
+                }),
+            };
+
+            Ok(JsonRpcResponse::Success {
+                id: 1,
+                result: serde_json::from_value(page)?,
+            })
+        }
+    }
+
+    /// Only events with the selector `1` are known.
+    #[derive(Debug, PartialEq)]
+    struct TestEvent(FieldElement);
+
+    impl TryFrom<EmittedEvent> for TestEvent {
+        type Error = EventError;
+
+        fn try_from(event: EmittedEvent) -> Result<Self, Self::Error> {
+            if event.keys[0] == FieldElement::ONE {
+                Ok(TestEvent(event.data[0]))
+            } else {
+                Err(EventError::UnknownSelector {
+                    selector: event.keys[0],
+                })
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_event_stream_pages() {
+        let provider = JsonRpcClient::new(MockTransport);
+        let filter = EventFilter {
+            from_block: None,
+            to_block: None,
+            address: Some(FieldElement::ONE),
+            keys: None,
+        };
+
+        let events: Vec<Result<DecodedEvent<TestEvent>, EventStreamError>> =
+            event_stream(&provider, filter, 2).collect().await;
+
+        assert_eq!(events.len(), 3);
+
+        let first = events[0].as_ref().unwrap();
+        assert_eq!(first.event, TestEvent(FieldElement::from(10_u32)));
+        assert_eq!(first.block_number, 1);
+        assert_eq!(first.transaction_hash, FieldElement::THREE);
+
+        assert!(matches!(
+            events[1],
+            Err(EventStreamError::Decode(EventError::UnknownSelector { .. }))
+        ));
+
+        let last = events[2].as_ref().unwrap();
+        assert_eq!(last.event, TestEvent(FieldElement::from(30_u32)));
+        assert_eq!(last.block_number, 2);
+    }
+}
@@ -24,7 +24,15 @@ mod cairo_function;
 pub use cairo_function::CairoFunction;
 
 mod cairo_event;
-pub use cairo_event::{CairoEvent, CairoEventInner};
+pub use cairo_event::{CairoEvent, CairoEventInner, EventDecodingMode, EventError, EventFieldKind};
 
 pub mod cairo_types;
 pub use cairo_types::CairoType;
+
+pub mod event_stream;
+
+pub mod receipt;
+
+pub mod messaging;
+
+pub mod src5;
@@ -0,0 +1,109 @@
+//! Messages sent from L1 to the `#[l1_handler]` functions of a contract.
+//!
+//! The generated `Reader` of a contract builds those messages
+//! from the typed arguments of each L1 handler.
+use sha3::{Digest, Keccak256};
+use starknet::core::types::eth_address::FromFieldElementError;
+use starknet::core::types::{EthAddress as CoreEthAddress, FieldElement, MsgFromL1};
+
+use super::cairo_types::EthAddress;
+
+/// A message sent by a L1 contract to a L1 handler of a L2 contract.
+#[derive(Debug, Clone, PartialEq)]
+pub struct L1ToL2Message {
+    /// Address of the L1 contract sending the message.
+    pub from_address: EthAddress,
+    /// Address of the L2 contract receiving the message.
+    pub to_address: FieldElement,
+    /// Selector of the L1 handler.
+    pub selector: FieldElement,
+    /// Arguments of the L1 handler, excluding `from_address`.
+    pub payload: Vec<FieldElement>,
+}
+
+impl L1ToL2Message {
+    /// Computes the hash of the message sent with the given nonce, as
+    /// computed by the Starknet core contract on L1: the keccak256 of the
+    /// `from_address`, `to_address`, `nonce`, `selector`, the payload
+    /// length and the payload, each encoded on 32 big-endian bytes.
+    pub fn hash(&self, nonce: FieldElement) -> [u8; 32] {
+        let mut hasher = Keccak256::new();
+
+        hasher.update(self.from_address.0.to_bytes_be());
+        hasher.update(self.to_address.to_bytes_be());
+        hasher.update(nonce.to_bytes_be());
+        hasher.update(self.selector.to_bytes_be());
+        hasher.update(FieldElement::from(self.payload.len()).to_bytes_be());
+
+        for felt in &self.payload {
+            hasher.update(felt.to_bytes_be());
+        }
+
+        hasher.finalize().into()
+    }
+
+    /// Converts into a `MsgFromL1`, used by the provider to estimate the
+    /// fee of the message. Fails if `from_address` is not a valid
+    /// Ethereum address.
+    pub fn to_msg_from_l1(&self) -> Result<MsgFromL1, FromFieldElementError> {
+        Ok(MsgFromL1 {
+            from_address: CoreEthAddress::from_felt(&self.from_address.0)?,
+            to_address: self.to_address,
+            entry_point_selector: self.selector,
+            payload: self.payload.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn felt(hex: &str) -> FieldElement {
+        FieldElement::from_hex_be(hex).unwrap()
+    }
+
+    #[test]
+    fn test_l1_to_l2_message_hash() {
+        // Deposit on the Starknet Goerli ETH bridge.
+        let msg = L1ToL2Message {
+            from_address: EthAddress(felt("0xc3511006C04EF1d78af4C8E0e74Ec18A6E64Ff9e")),
+            to_address: felt("0x73314940630fd6dcda0d772d4c972c4e0a9946bef9dabf4ef84eda8ef542b82"),
+            selector: felt("0x2d757788a8d8d6f21d1cd40bce38a8222d70654214e96ff95d8086e684fbee5"),
+            payload: vec![
+                felt("0x689ead7d814e51ed93644bc145f0754839b8dcb340027ce0c30953f38f55d7"),
+                felt("0x2c68af0bb140000"),
+                felt("0x0"),
+            ],
+        };
+
+        let hash = msg.hash(FieldElement::from(775628_u32));
+        assert_eq!(
+            FieldElement::from_byte_slice_be(&hash[1..]).unwrap(),
+            felt("0x1a543ef9563ad2545342b390b67edfcddf9886aa36846cf70382362fc5fab3")
+        );
+        assert_eq!(hash[0], 0xc5);
+    }
+
+    #[test]
+    fn test_l1_to_l2_message_to_msg_from_l1() {
+        let msg = L1ToL2Message {
+            from_address: EthAddress(FieldElement::ONE),
+            to_address: FieldElement::TWO,
+            selector: FieldElement::THREE,
+            payload: vec![FieldElement::ONE],
+        };
+
+        let core_msg = msg.to_msg_from_l1().unwrap();
+        assert_eq!(core_msg.from_address.as_bytes()[19], 1);
+        assert_eq!(core_msg.to_address, FieldElement::TWO);
+        assert_eq!(core_msg.entry_point_selector, FieldElement::THREE);
+        assert_eq!(core_msg.payload, vec![FieldElement::ONE]);
+
+        let invalid = L1ToL2Message {
+            from_address: EthAddress(FieldElement::MAX),
+            ..msg
+        };
+        assert!(invalid.to_msg_from_l1().is_err());
+    }
+}
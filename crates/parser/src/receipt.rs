@@ -0,0 +1,131 @@
+//! Decoding of the events emitted in a transaction receipt.
+//!
+//! The generated `Reader` of a contract uses those functions to return
+//! the events of the contract emitted by a given transaction.
+use starknet::core::types::{
+    Event, FieldElement, MaybePendingTransactionReceipt, PendingTransactionReceipt,
+    TransactionReceipt,
+};
+use starknet::providers::ProviderError;
+
+use super::EventError;
+
+/// An error raised while decoding the events of a transaction.
+#[derive(Debug, thiserror::Error)]
+pub enum ReceiptEventsError {
+    #[error("Could not fetch transaction receipt: {0}")]
+    Provider(#[from] ProviderError),
+    #[error(transparent)]
+    Decode(#[from] EventError),
+}
+
+/// Returns all the events of the receipt, in the order of emission.
+pub fn receipt_events(receipt: &MaybePendingTransactionReceipt) -> &[Event] {
+    match receipt {
+        MaybePendingTransactionReceipt::Receipt(r) => match r {
+            TransactionReceipt::Invoke(r) => &r.events,
+            TransactionReceipt::L1Handler(r) => &r.events,
+            TransactionReceipt::Declare(r) => &r.events,
+            TransactionReceipt::Deploy(r) => &r.events,
+            TransactionReceipt::DeployAccount(r) => &r.events,
+        },
+        MaybePendingTransactionReceipt::PendingReceipt(r) => match r {
+            PendingTransactionReceipt::Invoke(r) => &r.events,
+            PendingTransactionReceipt::L1Handler(r) => &r.events,
+            PendingTransactionReceipt::Declare(r) => &r.events,
+            PendingTransactionReceipt::Deploy(r) => &r.events,
+            PendingTransactionReceipt::DeployAccount(r) => &r.events,
+        },
+    }
+}
+
+/// Decodes into `E` the events of the receipt emitted by `address`,
+/// in the order of emission.
+///
+/// Events emitted by other contracts are ignored, whereas an event of
+/// `address` that can't be decoded is an error.
+pub fn decode_receipt_events<E>(
+    receipt: &MaybePendingTransactionReceipt,
+    address: FieldElement,
+) -> Result<Vec<E>, EventError>
+where
+    E: TryFrom<Event, Error = EventError>,
+{
+    receipt_events(receipt)
+        .iter()
+        .filter(|e| e.from_address == address)
+        .map(|e| E::try_from(e.clone()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starknet::core::types::{ExecutionResult, PendingInvokeTransactionReceipt};
+
+    /// Only events with the selector `1` are known.
+    #[derive(Debug, PartialEq)]
+    struct TestEvent(FieldElement);
+
+    impl TryFrom<Event> for TestEvent {
+        type Error = EventError;
+
+        fn try_from(event: Event) -> Result<Self, Self::Error> {
+            if event.keys[0] == FieldElement::ONE {
+                Ok(TestEvent(event.data[0]))
+            } else {
+                Err(EventError::UnknownSelector {
+                    selector: event.keys[0],
+                })
+            }
+        }
+    }
+
+    fn event(from_address: FieldElement, selector: FieldElement, value: u32) -> Event {
+        Event {
+            from_address,
+            keys: vec![selector],
+            data: vec![FieldElement::from(value)],
+        }
+    }
+
+    fn receipt(events: Vec<Event>) -> MaybePendingTransactionReceipt {
+        MaybePendingTransactionReceipt::PendingReceipt(PendingTransactionReceipt::Invoke(
+            PendingInvokeTransactionReceipt {
+                transaction_hash: FieldElement::ONE,
+                actual_fee: FieldElement::ZERO,
+                messages_sent: vec![],
+                events,
+                execution_result: ExecutionResult::Succeeded,
+            },
+        ))
+    }
+
+    #[test]
+    fn test_decode_receipt_events() {
+        let r = receipt(vec![
+            event(FieldElement::TWO, FieldElement::ONE, 1),
+            event(FieldElement::THREE, FieldElement::TWO, 2),
+            event(FieldElement::TWO, FieldElement::ONE, 3),
+        ]);
+
+        assert_eq!(receipt_events(&r).len(), 3);
+
+        let events: Vec<TestEvent> = decode_receipt_events(&r, FieldElement::TWO).unwrap();
+        assert_eq!(
+            events,
+            vec![TestEvent(FieldElement::ONE), TestEvent(FieldElement::THREE)]
+        );
+    }
+
+    #[test]
+    fn test_decode_receipt_events_unknown() {
+        let r = receipt(vec![
+            event(FieldElement::TWO, FieldElement::ONE, 1),
+            event(FieldElement::TWO, FieldElement::TWO, 2),
+        ]);
+
+        let res: Result<Vec<TestEvent>, _> = decode_receipt_events(&r, FieldElement::TWO);
+        assert!(matches!(res, Err(EventError::UnknownSelector { .. })));
+    }
+}
@@ -0,0 +1,278 @@
+//! SRC5 interface IDs, computed from the interfaces of an ABI.
+//!
+//! The ID of an interface is the XOR of the extended function selectors
+//! of it's functions. An extended function selector is the starknet keccak
+//! of the function signature, `name(input_1,input_2,...)->output`, where
+//! each type is represented by it's structure:
+//!
+//! * Structs are represented as tuples of their members, like `(u128,u128)`
+//!   for `u256` or `(@Array<felt252>)` for `Span<felt252>`.
+//! * Enums are represented as `E(variant_1,variant_2,...)`.
+//! * Tuples are represented as `(item_1,item_2,...)`.
+//! * Other core types are represented by their name, like `felt252`
+//!   or `Array<felt252>`.
+//!
+//! Functions without output have no `->output` part.
+//!
+//! https://github.com/starknet-io/SNIPs/blob/main/SNIPS/snip-5.md
+use starknet::core::types::contract::{AbiEntry, AbiFunction, AbiInterface};
+use starknet::core::types::FieldElement;
+use starknet::core::utils::starknet_keccak;
+use std::collections::HashMap;
+
+use crate::abi_types::{AbiType, AbiTypeAny, ParseError};
+
+/// Core types represented by their name.
+const CORE_NAMED_TYPES: [&str; 16] = [
+    "felt252",
+    "u8",
+    "u16",
+    "u32",
+    "u64",
+    "u128",
+    "i8",
+    "i16",
+    "i32",
+    "i64",
+    "i128",
+    "usize",
+    "bytes31",
+    "ContractAddress",
+    "ClassHash",
+    "StorageAddress",
+];
+
+/// An error raised while computing an interface ID.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum InterfaceIdError {
+    /// A type of the interface is not a valid cairo type.
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+    /// A type of the interface is neither a core type,
+    /// nor a struct or an enum declared in the ABI.
+    #[error("Unknown type {0} in the interface signature.")]
+    UnknownType(String),
+}
+
+/// Computes the SRC5 ID of the interface. The structs and enums
+/// used by the interface are resolved from the ABI entries.
+pub fn interface_id(
+    interface: &AbiInterface,
+    abi: &[AbiEntry],
+) -> Result<FieldElement, InterfaceIdError> {
+    let signatures = TypeSignatures::new(abi);
+    let mut id = [0_u8; 32];
+
+    for entry in &interface.items {
+        let AbiEntry::Function(f) = entry else {
+            continue;
+        };
+
+        let selector = starknet_keccak(signatures.function(f)?.as_bytes()).to_bytes_be();
+        for (b, s) in id.iter_mut().zip(selector) {
+            *b ^= s;
+        }
+    }
+
+    // The XOR of felts below 2^250 can't overflow the field.
+    Ok(FieldElement::from_bytes_be(&id).expect("Interface ID must fit into a felt"))
+}
+
+/// Returns the signature of the function used to compute
+/// it's extended function selector.
+pub fn function_signature(f: &AbiFunction, abi: &[AbiEntry]) -> Result<String, InterfaceIdError> {
+    TypeSignatures::new(abi).function(f)
+}
+
+/// Resolves the type signatures, from the structs and
+/// enums declared in the ABI.
+struct TypeSignatures {
+    /// Types of the members/variants, indexed by struct/enum full type.
+    structs: HashMap<String, Vec<String>>,
+    enums: HashMap<String, Vec<String>>,
+}
+
+impl TypeSignatures {
+    fn new(abi: &[AbiEntry]) -> Self {
+        let mut structs = HashMap::new();
+        let mut enums = HashMap::new();
+
+        for entry in abi {
+            match entry {
+                AbiEntry::Struct(s) => {
+                    let members = s.members.iter().map(|m| m.r#type.clone()).collect();
+                    structs.insert(s.name.clone(), members);
+                }
+                AbiEntry::Enum(e) => {
+                    let variants = e.variants.iter().map(|v| v.r#type.clone()).collect();
+                    enums.insert(e.name.clone(), variants);
+                }
+                _ => (),
+            }
+        }
+
+        TypeSignatures { structs, enums }
+    }
+
+    fn function(&self, f: &AbiFunction) -> Result<String, InterfaceIdError> {
+        let inputs = f
+            .inputs
+            .iter()
+            .map(|i| self.type_string(&i.r#type))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let outputs = f
+            .outputs
+            .iter()
+            .map(|o| self.type_string(&o.r#type))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let output = match outputs.len() {
+            0 => String::new(),
+            1 => format!("->{}", outputs[0]),
+            _ => format!("->({})", outputs.join(",")),
+        };
+
+        Ok(format!("{}({}){}", f.name, inputs.join(","), output))
+    }
+
+    fn type_string(&self, type_string: &str) -> Result<String, InterfaceIdError> {
+        self.signature(&AbiTypeAny::try_from_string(type_string)?)
+    }
+
+    fn signature(&self, abi_type: &AbiTypeAny) -> Result<String, InterfaceIdError> {
+        let full = abi_type.get_cairo_type_full();
+
+        match abi_type {
+            AbiTypeAny::Tuple(t) => Ok(format!("({})", self.signatures(&t.inners)?)),
+            AbiTypeAny::Array(a) => {
+                let inner = self.signature(&a.inner)?;
+                // `Span` is a struct wrapping a snapshot of the array.
+                if a.cairo_type.ends_with("Span") {
+                    Ok(format!("(@Array<{}>)", inner))
+                } else {
+                    Ok(format!("Array<{}>", inner))
+                }
+            }
+            AbiTypeAny::Generic(g) => match g.cairo_type.as_str() {
+                "core::option::Option" => Ok(format!("E({},())", self.signatures(&g.inners)?)),
+                "core::result::Result" => Ok(format!("E({})", self.signatures(&g.inners)?)),
+                _ => self.declared(&full),
+            },
+            AbiTypeAny::Basic(_) => match full.as_str() {
+                "()" => Ok(full),
+                "core::bool" => Ok("E((),())".to_string()),
+                "core::integer::u256" => Ok("(u128,u128)".to_string()),
+                "core::byte_array::ByteArray" => Ok("(Array<bytes31>,felt252,u32)".to_string()),
+                "core::starknet::eth_address::EthAddress" => Ok("(felt252)".to_string()),
+                _ => {
+                    let name = abi_type.get_cairo_type_name();
+                    if full.starts_with("core::") && CORE_NAMED_TYPES.contains(&name.as_str()) {
+                        Ok(name)
+                    } else {
+                        self.declared(&full)
+                    }
+                }
+            },
+        }
+    }
+
+    fn signatures(&self, types: &[AbiTypeAny]) -> Result<String, InterfaceIdError> {
+        Ok(types
+            .iter()
+            .map(|t| self.signature(t))
+            .collect::<Result<Vec<_>, _>>()?
+            .join(","))
+    }
+
+    /// Signature of a struct or enum declared in the ABI.
+    fn declared(&self, full: &str) -> Result<String, InterfaceIdError> {
+        let (prefix, types) = match (self.structs.get(full), self.enums.get(full)) {
+            (Some(members), _) => ("", members),
+            (_, Some(variants)) => ("E", variants),
+            _ => return Err(InterfaceIdError::UnknownType(full.to_string())),
+        };
+
+        let types = types
+            .iter()
+            .map(|t| self.type_string(t))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(format!("{}({})", prefix, types.join(",")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starknet::macros::felt;
+
+    fn abi(json: &str) -> Vec<AbiEntry> {
+        serde_json::from_str(json).unwrap()
+    }
+
+    fn interface(abi: &[AbiEntry]) -> &AbiInterface {
+        abi.iter()
+            .find_map(|e| match e {
+                AbiEntry::Interface(i) => Some(i),
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn test_interface_id_src5() {
+        let abi = abi(r#"[{"type":"interface","name":"ISRC5","items":[
+            {"type":"function","name":"supports_interface",
+             "inputs":[{"name":"interface_id","type":"core::felt252"}],
+             "outputs":[{"type":"core::bool"}],"state_mutability":"view"}
+        ]}]"#);
+
+        assert_eq!(
+            interface_id(interface(&abi), &abi).unwrap(),
+            felt!("0x3f918d17e5ee77373b56385708f855659a07f75997f365cf87748628532a055")
+        );
+    }
+
+    #[test]
+    fn test_function_signature() {
+        let abi = abi(r#"[
+            {"type":"struct","name":"m::Point","members":[
+                {"name":"x","type":"core::felt252"},
+                {"name":"y","type":"core::integer::u256"}]},
+            {"type":"enum","name":"m::Dir","variants":[
+                {"name":"Up","type":"()"},
+                {"name":"Down","type":"core::option::Option::<core::integer::u8>"}]},
+            {"type":"function","name":"move",
+             "inputs":[
+                {"name":"p","type":"core::array::Span::<m::Point>"},
+                {"name":"d","type":"(m::Dir, core::starknet::contract_address::ContractAddress)"}],
+             "outputs":[],"state_mutability":"external"}
+        ]"#);
+
+        let AbiEntry::Function(f) = &abi[2] else {
+            panic!("Expected function");
+        };
+
+        assert_eq!(
+            function_signature(f, &abi).unwrap(),
+            "move((@Array<(felt252,(u128,u128))>),(E((),E(u8,())),ContractAddress))"
+        );
+    }
+
+    #[test]
+    fn test_function_signature_unknown_type() {
+        let abi = abi(r#"[{"type":"function","name":"f",
+            "inputs":[{"name":"a","type":"m::Unknown"}],
+            "outputs":[],"state_mutability":"view"}]"#);
+
+        let AbiEntry::Function(f) = &abi[0] else {
+            panic!("Expected function");
+        };
+
+        assert_eq!(
+            function_signature(f, &abi),
+            Err(InterfaceIdError::UnknownType("m::Unknown".to_string()))
+        );
+    }
+}
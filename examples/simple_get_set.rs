@@ -5,6 +5,7 @@ use starknet::{
     signers::{LocalWallet, SigningKey},
 };
 use starknet_abigen::macros::abigen;
+use starknet_abigen::parser::cairo_types::U256;
 use std::sync::Arc;
 use url::Url;
 
@@ -89,7 +90,7 @@ async fn main() {
 
     // Now let's say we want to do multicall, and in one transaction we want to set a and b.
     let call_set_a = contract.set_a_getcall(&FieldElement::from_hex_be("0xee").unwrap());
-    let call_set_b = contract.set_b_getcall(&u256 { low: 0xff, high: 0 });
+    let call_set_b = contract.set_b_getcall(&U256::from(0xff_u128));
 
     let r = contract
         .account
@@ -131,10 +132,7 @@ async fn other_func<A: ConnectedAccount + Sync>(contract: Arc<MyContract<A>>) {
     // passing a contract you also have the reader that you can retrieve anytime
     // by calling `contract.reader()`.
     let set_b = contract
-        .set_b(&u256 {
-            low: 0x1234,
-            high: 0,
-        })
+        .set_b(&U256::from(0x1234_u128))
         .await
         .expect("Call to `set_b` failed");
 
@@ -7,3 +7,12 @@ pub mod parser {
 pub mod macros {
     pub use starknet_abigen_macros::*;
 }
+
+#[cfg(any(
+    feature = "erc20",
+    feature = "erc721",
+    feature = "erc1155",
+    feature = "src5",
+    feature = "src6"
+))]
+pub mod standards;
@@ -0,0 +1,5 @@
+//! ERC1155 multi token, with the `IERC1155`, `IERC1155MetadataURI`
+//! and `IERC1155Camel` interfaces.
+use starknet::core::types::FieldElement;
+
+crate::macros::abigen!(ERC1155, "./abis/erc1155.json");
@@ -0,0 +1,5 @@
+//! ERC20 fungible token, with the `IERC20`, `IERC20Metadata`
+//! and `IERC20CamelOnly` interfaces.
+use starknet::core::types::FieldElement;
+
+crate::macros::abigen!(ERC20, "./abis/erc20.json");
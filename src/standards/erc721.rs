@@ -0,0 +1,5 @@
+//! ERC721 non-fungible token, with the `IERC721`, `IERC721Metadata`,
+//! `IERC721CamelOnly` and `IERC721MetadataCamelOnly` interfaces.
+use starknet::core::types::FieldElement;
+
+crate::macros::abigen!(ERC721, "./abis/erc721.json");
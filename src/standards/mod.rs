@@ -0,0 +1,16 @@
+//! Prebuilt bindings for the standard Starknet interfaces.
+//!
+//! Each standard is enabled with the cargo feature of the same name,
+//! and is generated from the ABI of the OpenZeppelin implementation
+//! bundled in the `abis` folder. The bindings, including the events,
+//! can be used with any contract compliant with the standard.
+#[cfg(feature = "erc1155")]
+pub mod erc1155;
+#[cfg(feature = "erc20")]
+pub mod erc20;
+#[cfg(feature = "erc721")]
+pub mod erc721;
+#[cfg(feature = "src5")]
+pub mod src5;
+#[cfg(feature = "src6")]
+pub mod src6;
@@ -0,0 +1,4 @@
+//! SRC5 interface detection, with the `ISRC5` interface.
+use starknet::core::types::FieldElement;
+
+crate::macros::abigen!(SRC5, "./abis/src5.json");
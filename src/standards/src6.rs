@@ -0,0 +1,4 @@
+//! SRC6 account, with the `ISRC6` and `ISRC6CamelOnly` interfaces.
+use starknet::core::types::FieldElement;
+
+crate::macros::abigen!(SRC6, "./abis/src6.json");
@@ -0,0 +1,28 @@
+//! Types sharing the same name in different cairo modules.
+use starknet::core::types::FieldElement;
+use starknet_abigen::parser::CairoType;
+
+mod positions {
+    use starknet::core::types::FieldElement;
+    starknet_abigen::macros::abigen!(PositionsContract, "./tests/abis/positions.json");
+}
+
+use positions::{APosition, BPosition};
+
+#[test]
+fn test_same_name_types_aliased() {
+    let a = APosition {
+        x: FieldElement::ONE,
+    };
+    assert_eq!(APosition::serialize(&a), vec![FieldElement::ONE]);
+
+    let b = BPosition { x: 2, y: 3 };
+    assert_eq!(
+        BPosition::serialize(&b),
+        vec![FieldElement::TWO, FieldElement::THREE]
+    );
+    assert_eq!(
+        BPosition::deserialize(&[FieldElement::TWO, FieldElement::THREE], 0).unwrap(),
+        b
+    );
+}
@@ -0,0 +1,69 @@
+//! Deployment functions generated by the `abigen` macro from the constructor.
+use starknet::accounts::{ExecutionEncoding, SingleOwnerAccount};
+use starknet::core::types::FieldElement;
+use starknet::core::utils::{get_udc_deployed_address, UdcUniqueSettings, UdcUniqueness};
+use starknet::macros::felt;
+use starknet::providers::{jsonrpc::HttpTransport, JsonRpcClient};
+use starknet::signers::{LocalWallet, SigningKey};
+use starknet_abigen::parser::cairo_types::ContractAddress;
+use url::Url;
+
+mod constructor {
+    use starknet::core::types::FieldElement;
+    starknet_abigen::macros::abigen!(TokenContract, "./tests/abis/constructor.json");
+}
+
+use constructor::TokenContract;
+
+const UDC_ADDRESS: FieldElement =
+    felt!("0x041a78e741e5af2fec34b695679bc6891742439f7afb8484ecd7766661ad02bf");
+
+fn account() -> SingleOwnerAccount<JsonRpcClient<HttpTransport>, LocalWallet> {
+    SingleOwnerAccount::new(
+        JsonRpcClient::new(HttpTransport::new(
+            Url::parse("http://localhost:5050").unwrap(),
+        )),
+        LocalWallet::from(SigningKey::from_secret_scalar(FieldElement::ONE)),
+        FieldElement::from(0x1234_u32),
+        FieldElement::TWO,
+        ExecutionEncoding::Legacy,
+    )
+}
+
+#[test]
+fn test_deployed_address() {
+    let account = account();
+    let class_hash = FieldElement::from(0xc1a55_u32);
+    let salt = FieldElement::from(42_u32);
+
+    // The constructor `salt` argument is renamed, as it collides
+    // with the deployment salt.
+    let owner = ContractAddress(FieldElement::ONE);
+    let supply = 1000_u32.into();
+    let salt_ = FieldElement::THREE;
+
+    let calldata = vec![
+        FieldElement::ONE,
+        FieldElement::from(1000_u32),
+        FieldElement::ZERO,
+        FieldElement::THREE,
+    ];
+
+    assert_eq!(
+        TokenContract::deployed_address(&account, class_hash, salt, false, &owner, &supply, &salt_),
+        get_udc_deployed_address(salt, class_hash, &UdcUniqueness::NotUnique, &calldata)
+    );
+
+    assert_eq!(
+        TokenContract::deployed_address(&account, class_hash, salt, true, &owner, &supply, &salt_),
+        get_udc_deployed_address(
+            salt,
+            class_hash,
+            &UdcUniqueness::Unique(UdcUniqueSettings {
+                deployer_address: FieldElement::from(0x1234_u32),
+                udc_contract_address: UDC_ADDRESS,
+            }),
+            &calldata
+        )
+    );
+}
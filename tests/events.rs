@@ -0,0 +1,484 @@
+//! Round-trip tests between the events encoding and decoding
+//! generated by the `abigen` macro.
+use starknet::core::types::{
+    EmittedEvent, Event as ReceiptEvent, ExecutionResult, FieldElement,
+    MaybePendingTransactionReceipt, PendingInvokeTransactionReceipt, PendingTransactionReceipt,
+};
+use starknet::macros::selector;
+use starknet::providers::{jsonrpc::HttpTransport, JsonRpcClient};
+use starknet_abigen::parser::{
+    cairo_types::{ContractAddress, Error},
+    CairoType, EventDecodingMode, EventError,
+};
+use url::Url;
+
+mod event {
+    use starknet::core::types::FieldElement;
+    starknet_abigen::macros::abigen!(EventContract, "./contracts/abi/event.json");
+}
+
+mod components {
+    use starknet::core::types::FieldElement;
+    starknet_abigen::macros::abigen!(ComponentsContract, "./tests/abis/components.json");
+}
+
+mod renamed {
+    use starknet::core::types::FieldElement;
+    starknet_abigen::macros::abigen!(RenamedContract, "./tests/abis/renamed_events.json");
+}
+
+mod untyped {
+    use starknet::core::types::FieldElement;
+    starknet_abigen::macros::abigen!(UntypedContract, "./tests/abis/untyped_events.json");
+}
+
+use components::{
+    CounterComponentEvent, Incremented, OwnableComponentEvent, OwnershipTransferred, Transfer,
+};
+use event::{Event, MyEventA, MyEventB, MyEventC};
+
+fn to_emitted_event(keys: Vec<FieldElement>, data: Vec<FieldElement>) -> EmittedEvent {
+    EmittedEvent {
+        from_address: FieldElement::ONE,
+        keys,
+        data,
+        block_hash: FieldElement::TWO,
+        block_number: 1,
+        transaction_hash: FieldElement::THREE,
+    }
+}
+
+#[test]
+fn test_event_a_round_trip() {
+    let ev = Event::MyEventA(MyEventA {
+        header: FieldElement::from(0x1234_u32),
+        value: vec![FieldElement::ONE, FieldElement::TWO],
+    });
+
+    let (keys, data) = ev.encode();
+    assert_eq!(
+        keys,
+        vec![selector!("MyEventA"), FieldElement::from(0x1234_u32)]
+    );
+    assert_eq!(
+        data,
+        vec![FieldElement::TWO, FieldElement::ONE, FieldElement::TWO]
+    );
+
+    let emitted = ev.to_emitted_event(FieldElement::ONE, FieldElement::TWO, 1, FieldElement::THREE);
+    assert_eq!(emitted.from_address, FieldElement::ONE);
+    assert_eq!(emitted.block_number, 1);
+    assert_eq!(emitted.keys, keys);
+    assert_eq!(emitted.data, data);
+    assert_eq!(Event::try_from(emitted).unwrap(), ev);
+    assert_eq!(Event::try_from(to_emitted_event(keys, data)).unwrap(), ev);
+}
+
+#[test]
+fn test_event_b_round_trip() {
+    let ev = Event::MyEventB(MyEventB {
+        value: FieldElement::from(42_u32),
+    });
+
+    let (keys, data) = ev.encode();
+    assert_eq!(keys, vec![selector!("MyEventB")]);
+    assert_eq!(data, vec![FieldElement::from(42_u32)]);
+    assert_eq!(
+        Event::decode(&keys, &data, EventDecodingMode::Strict).unwrap(),
+        ev
+    );
+}
+
+#[test]
+fn test_event_c_round_trip() {
+    let ev = Event::MyEventC(MyEventC {
+        v1: FieldElement::ONE,
+        v2: FieldElement::TWO,
+        v3: FieldElement::THREE,
+        v4: ContractAddress(FieldElement::from(0xabc_u32)),
+    });
+
+    let (keys, data) = ev.encode();
+    assert_eq!(
+        keys,
+        vec![selector!("MyEventC"), FieldElement::ONE, FieldElement::TWO]
+    );
+    assert_eq!(
+        data,
+        vec![FieldElement::THREE, FieldElement::from(0xabc_u32)]
+    );
+    assert_eq!(
+        Event::decode(&keys, &data, EventDecodingMode::Strict).unwrap(),
+        ev
+    );
+}
+
+#[test]
+fn test_flat_component_event_round_trip() {
+    let ev = components::Event::OwnableEvent(OwnableComponentEvent::OwnershipTransferred(
+        OwnershipTransferred {
+            previous_owner: ContractAddress(FieldElement::ONE),
+            new_owner: ContractAddress(FieldElement::TWO),
+        },
+    ));
+
+    // Flat events have no selector for the component variant.
+    let (keys, data) = ev.encode();
+    assert_eq!(
+        keys,
+        vec![
+            selector!("OwnershipTransferred"),
+            FieldElement::ONE,
+            FieldElement::TWO
+        ]
+    );
+    assert!(data.is_empty());
+    assert_eq!(
+        components::Event::decode(&keys, &data, EventDecodingMode::Strict).unwrap(),
+        ev
+    );
+}
+
+#[test]
+fn test_nested_component_event_round_trip() {
+    let ev =
+        components::Event::CounterEvent(CounterComponentEvent::Incremented(Incremented { by: 3 }));
+
+    let (keys, data) = ev.encode();
+    assert_eq!(
+        keys,
+        vec![selector!("CounterEvent"), selector!("Incremented")]
+    );
+    assert_eq!(data, vec![FieldElement::THREE]);
+    assert_eq!(
+        components::Event::decode(&keys, &data, EventDecodingMode::Strict).unwrap(),
+        ev
+    );
+}
+
+#[test]
+fn test_transfer_event_round_trip() {
+    let ev = components::Event::Transfer(Transfer {
+        from: FieldElement::ONE,
+        amount: 1000_u32.into(),
+        memo: vec![FieldElement::TWO],
+    });
+
+    let emitted = ev.to_emitted_event(FieldElement::ONE, FieldElement::TWO, 1, FieldElement::THREE);
+    assert_eq!(components::Event::try_from(emitted).unwrap(), ev);
+}
+
+#[test]
+fn test_event_filter_keys() {
+    assert_eq!(
+        event::MyEventCFilter::new().build(),
+        vec![vec![selector!("MyEventC")]]
+    );
+
+    assert_eq!(
+        event::MyEventCFilter::new().v2(&FieldElement::TWO).build(),
+        vec![vec![selector!("MyEventC")], vec![], vec![FieldElement::TWO]]
+    );
+
+    assert_eq!(
+        components::OwnershipTransferredFilter::new()
+            .previous_owner(&ContractAddress(FieldElement::ONE))
+            .build(),
+        vec![
+            vec![selector!("OwnershipTransferred")],
+            vec![FieldElement::ONE]
+        ]
+    );
+}
+
+#[test]
+fn test_event_filter_selectors() {
+    let keys = components::IncrementedFilter::new()
+        .selectors(vec![selector!("CounterEvent"), selector!("Incremented")])
+        .build();
+
+    assert_eq!(
+        keys,
+        vec![
+            vec![selector!("CounterEvent")],
+            vec![selector!("Incremented")]
+        ]
+    );
+}
+
+#[test]
+fn test_events_from_receipt() {
+    let ev_a = Event::MyEventB(MyEventB {
+        value: FieldElement::ONE,
+    });
+    let ev_b = Event::MyEventB(MyEventB {
+        value: FieldElement::TWO,
+    });
+
+    let to_receipt_event = |from_address: FieldElement, ev: &Event| {
+        let (keys, data) = ev.encode();
+        ReceiptEvent {
+            from_address,
+            keys,
+            data,
+        }
+    };
+
+    let receipt = MaybePendingTransactionReceipt::PendingReceipt(
+        PendingTransactionReceipt::Invoke(PendingInvokeTransactionReceipt {
+            transaction_hash: FieldElement::ONE,
+            actual_fee: FieldElement::ZERO,
+            messages_sent: vec![],
+            events: vec![
+                to_receipt_event(FieldElement::TWO, &ev_a),
+                // Any event of an other contract is ignored.
+                ReceiptEvent {
+                    from_address: FieldElement::THREE,
+                    keys: vec![FieldElement::ONE],
+                    data: vec![],
+                },
+                to_receipt_event(FieldElement::TWO, &ev_b),
+            ],
+            execution_result: ExecutionResult::Succeeded,
+        }),
+    );
+
+    let provider = JsonRpcClient::new(HttpTransport::new(
+        Url::parse("http://localhost:5050").unwrap(),
+    ));
+    let reader = event::EventContractReader::new(FieldElement::TWO, &provider);
+
+    assert_eq!(
+        reader.events_from_receipt(&receipt).unwrap(),
+        vec![ev_a, ev_b]
+    );
+}
+
+#[test]
+fn test_untyped_event_round_trip() {
+    let ev = untyped::Event::Transfer(untyped::Transfer {
+        from: ContractAddress(FieldElement::ONE),
+        to: ContractAddress(FieldElement::TWO),
+        amount: 5_u32.into(),
+    });
+
+    // Untyped events only have their selector as key.
+    let (keys, data) = ev.encode();
+    assert_eq!(keys, vec![selector!("Transfer")]);
+    assert_eq!(
+        data,
+        vec![
+            FieldElement::ONE,
+            FieldElement::TWO,
+            FieldElement::from(5_u32),
+            FieldElement::ZERO
+        ]
+    );
+
+    let emitted = to_emitted_event(keys, data);
+    assert_eq!(untyped::Event::try_from(emitted).unwrap(), ev);
+
+    let paused = to_emitted_event(vec![selector!("Paused")], vec![]);
+    assert_eq!(
+        untyped::Event::try_from(paused).unwrap(),
+        untyped::Event::Paused(untyped::Paused {})
+    );
+}
+
+#[test]
+fn test_memberless_struct_array_forged_length() {
+    // `Paused` has no member, and doesn't consume any felt.
+    let felts = vec![FieldElement::TWO, FieldElement::ONE];
+    assert_eq!(
+        Vec::<untyped::Paused>::deserialize(&felts, 0).unwrap(),
+        vec![untyped::Paused {}, untyped::Paused {}]
+    );
+
+    let felts = vec![FieldElement::from(u64::MAX)];
+    assert!(matches!(
+        Vec::<untyped::Paused>::deserialize(&felts, 0),
+        Err(Error::Deserialize(_))
+    ));
+}
+
+#[test]
+fn test_event_filter_variant_selector() {
+    // The struct is emitted with the selector of it's variant.
+    let ev = renamed::Event::TokenTransfer(renamed::Transfer {
+        from: FieldElement::ONE,
+        amount: FieldElement::TWO,
+    });
+    let (keys, _) = ev.encode();
+
+    let filter = renamed::TransferFilter::new()
+        .from(&FieldElement::ONE)
+        .build();
+    assert_eq!(
+        filter,
+        vec![vec![selector!("TokenTransfer")], vec![FieldElement::ONE]]
+    );
+    assert_eq!(keys[0], filter[0][0]);
+
+    // Keys following a nested member can't be filtered.
+    assert_eq!(
+        renamed::WrapperFilter::new().id(&FieldElement::ONE).build(),
+        vec![vec![selector!("Wrapper")], vec![FieldElement::ONE]]
+    );
+}
+
+/// Decoding function generated on the `Event` enums.
+type DecodeFn<E> = fn(&[FieldElement], &[FieldElement], EventDecodingMode) -> Result<E, EventError>;
+
+/// Asserts the event is decoded back from it's `EmittedEvent`,
+/// consuming all it's keys and data.
+fn assert_round_trip<E: PartialEq + std::fmt::Debug>(
+    ev: &E,
+    emitted: EmittedEvent,
+    decode: DecodeFn<E>,
+) {
+    assert_eq!(
+        &decode(&emitted.keys, &emitted.data, EventDecodingMode::Strict).unwrap(),
+        ev
+    );
+}
+
+#[test]
+fn test_event_contract_all_events_round_trip() {
+    let a = || MyEventA {
+        header: FieldElement::ONE,
+        value: vec![FieldElement::TWO],
+    };
+    let b = || MyEventB {
+        value: FieldElement::THREE,
+    };
+    let c = || MyEventC {
+        v1: FieldElement::ONE,
+        v2: FieldElement::TWO,
+        v3: FieldElement::THREE,
+        v4: ContractAddress(FieldElement::ONE),
+    };
+
+    // Struct events are encoded with the selector of their variant.
+    assert_eq!(a().encode(), Event::MyEventA(a()).encode());
+    assert_eq!(b().encode(), Event::MyEventB(b()).encode());
+    assert_eq!(c().encode(), Event::MyEventC(c()).encode());
+
+    for ev in [
+        Event::MyEventA(a()),
+        Event::MyEventB(b()),
+        Event::MyEventC(c()),
+    ] {
+        let emitted =
+            ev.to_emitted_event(FieldElement::ONE, FieldElement::TWO, 1, FieldElement::THREE);
+        assert_round_trip(&ev, emitted, Event::decode);
+    }
+}
+
+#[test]
+fn test_components_all_events_round_trip() {
+    let owned = || OwnershipTransferred {
+        previous_owner: ContractAddress(FieldElement::ONE),
+        new_owner: ContractAddress(FieldElement::TWO),
+    };
+    let incremented = || Incremented { by: 7 };
+    let transfer = || Transfer {
+        from: FieldElement::ONE,
+        amount: 9_u32.into(),
+        memo: vec![FieldElement::THREE],
+    };
+
+    assert_eq!(
+        transfer().encode(),
+        components::Event::Transfer(transfer()).encode()
+    );
+
+    // Events of components are encoded with the selector of their variant in the
+    // component enum only, the contract `Event` adding the component variant.
+    let (keys, _) = incremented().encode();
+    assert_eq!(keys, vec![selector!("Incremented")]);
+    let (keys, _) =
+        components::Event::CounterEvent(CounterComponentEvent::Incremented(incremented())).encode();
+    assert_eq!(
+        keys,
+        vec![selector!("CounterEvent"), selector!("Incremented")]
+    );
+
+    for ev in [
+        components::Event::OwnableEvent(OwnableComponentEvent::OwnershipTransferred(owned())),
+        components::Event::CounterEvent(CounterComponentEvent::Incremented(incremented())),
+        components::Event::Transfer(transfer()),
+    ] {
+        let emitted =
+            ev.to_emitted_event(FieldElement::ONE, FieldElement::TWO, 1, FieldElement::THREE);
+        assert_round_trip(&ev, emitted, components::Event::decode);
+    }
+
+    let (keys, data) = owned().encode();
+    assert_eq!(
+        OwnableComponentEvent::deserialize_event(&keys, &data, 0, 0).unwrap(),
+        (OwnableComponentEvent::OwnershipTransferred(owned()), 3, 0)
+    );
+    let (keys, data) = incremented().encode();
+    assert_eq!(
+        CounterComponentEvent::deserialize_event(&keys, &data, 0, 0).unwrap(),
+        (CounterComponentEvent::Incremented(incremented()), 1, 1)
+    );
+}
+
+#[test]
+fn test_renamed_events_all_events_round_trip() {
+    let transfer = || renamed::Transfer {
+        from: FieldElement::ONE,
+        amount: FieldElement::TWO,
+    };
+    let wrapper = || renamed::Wrapper {
+        id: FieldElement::THREE,
+        inner: transfer(),
+        tag: FieldElement::ONE,
+    };
+
+    assert_eq!(
+        transfer().encode(),
+        renamed::Event::TokenTransfer(transfer()).encode()
+    );
+    assert_eq!(
+        wrapper().encode(),
+        renamed::Event::Wrapper(wrapper()).encode()
+    );
+
+    for ev in [
+        renamed::Event::TokenTransfer(transfer()),
+        renamed::Event::Wrapper(wrapper()),
+    ] {
+        let emitted =
+            ev.to_emitted_event(FieldElement::ONE, FieldElement::TWO, 1, FieldElement::THREE);
+        assert_round_trip(&ev, emitted, renamed::Event::decode);
+    }
+}
+
+#[test]
+fn test_untyped_events_all_events_round_trip() {
+    let transfer = || untyped::Transfer {
+        from: ContractAddress(FieldElement::ONE),
+        to: ContractAddress(FieldElement::TWO),
+        amount: 5_u32.into(),
+    };
+
+    assert_eq!(
+        transfer().encode(),
+        untyped::Event::Transfer(transfer()).encode()
+    );
+    assert_eq!(
+        untyped::Paused {}.encode(),
+        (vec![selector!("Paused")], vec![])
+    );
+
+    for ev in [
+        untyped::Event::Transfer(transfer()),
+        untyped::Event::Paused(untyped::Paused {}),
+    ] {
+        let emitted =
+            ev.to_emitted_event(FieldElement::ONE, FieldElement::TWO, 1, FieldElement::THREE);
+        assert_round_trip(&ev, emitted, untyped::Event::decode);
+    }
+}
@@ -0,0 +1,90 @@
+//! Traits generated by the `abigen` macro for each cairo interface.
+use starknet::accounts::{Call, ExecutionEncoding, SingleOwnerAccount};
+use starknet::core::types::FieldElement;
+use starknet::macros::{felt, selector};
+use starknet::providers::{jsonrpc::HttpTransport, JsonRpcClient};
+use starknet::signers::{LocalWallet, SigningKey};
+use starknet_abigen::parser::cairo_types::ContractAddress;
+use url::Url;
+
+mod interfaces {
+    use starknet::core::types::FieldElement;
+    starknet_abigen::macros::abigen!(TokenContract, "./tests/abis/interfaces.json");
+}
+
+use interfaces::{
+    IERC20Reader, TokenContract, TokenContractReader, IERC20, IERC20_ID, IMINTABLE_ID, ISRC5_ID,
+};
+
+fn provider() -> JsonRpcClient<HttpTransport> {
+    JsonRpcClient::new(HttpTransport::new(
+        Url::parse("http://localhost:5050").unwrap(),
+    ))
+}
+
+/// Generic over any contract implementing `IERC20`.
+fn transfer_call<T: IERC20>(token: &T, recipient: &ContractAddress) -> Call {
+    token.transfer_getcall(recipient, &1000_u32.into())
+}
+
+fn is_erc20_reader<R: IERC20Reader>(_reader: &R) -> bool {
+    true
+}
+
+fn assert_send<F: Send>(_future: F) {}
+
+/// The futures of the traits must be `Send` to be spawned
+/// by code generic over the interface.
+fn assert_futures_send<T: IERC20, R: IERC20Reader>(token: &T, reader: &R) {
+    let recipient = ContractAddress(FieldElement::TWO);
+    assert_send(token.name());
+    assert_send(token.transfer(&recipient, &1000_u32.into()));
+    assert_send(reader.balance_of(&recipient));
+}
+
+#[test]
+fn test_interface_traits_implemented() {
+    let account = SingleOwnerAccount::new(
+        provider(),
+        LocalWallet::from(SigningKey::from_secret_scalar(FieldElement::ONE)),
+        FieldElement::ONE,
+        FieldElement::TWO,
+        ExecutionEncoding::Legacy,
+    );
+    let contract = TokenContract::new(FieldElement::THREE, account);
+
+    let call = transfer_call(&contract, &ContractAddress(FieldElement::TWO));
+    assert_eq!(call.to, FieldElement::THREE);
+    assert_eq!(call.selector, selector!("transfer"));
+    assert_eq!(
+        call.calldata,
+        vec![
+            FieldElement::TWO,
+            FieldElement::from(1000_u32),
+            FieldElement::ZERO
+        ]
+    );
+
+    let provider = provider();
+    let reader = TokenContractReader::new(FieldElement::THREE, &provider);
+    assert!(is_erc20_reader(&reader));
+
+    assert_futures_send(&contract, &reader);
+}
+
+#[test]
+fn test_interface_ids() {
+    // Canonical ID of SNIP-5.
+    assert_eq!(
+        ISRC5_ID,
+        felt!("0x3f918d17e5ee77373b56385708f855659a07f75997f365cf87748628532a055")
+    );
+    assert_eq!(
+        IERC20_ID,
+        felt!("0x1864745d9ee4b0ab7025e4036d1ab7aa09406e7841e45c5d1302d2e2472c09d")
+    );
+    assert_eq!(
+        IMINTABLE_ID,
+        felt!("0x10e00f36251ae6fe209c4db4a565e5bb543d619fdebdf84bdbec1364ceceb96")
+    );
+}
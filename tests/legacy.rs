@@ -0,0 +1,128 @@
+//! Bindings generated by the `abigen` macro from a legacy (Cairo 0) ABI.
+use starknet::accounts::{ExecutionEncoding, SingleOwnerAccount};
+use starknet::core::types::{EmittedEvent, FieldElement};
+use starknet::macros::selector;
+use starknet::providers::{jsonrpc::HttpTransport, JsonRpcClient};
+use starknet::signers::{LocalWallet, SigningKey};
+use starknet_abigen::parser::CairoType;
+use url::Url;
+
+mod legacy {
+    use starknet::core::types::FieldElement;
+    starknet_abigen::macros::abigen!(LegacyContract, "./tests/abis/legacy.json");
+}
+
+use legacy::{Event, GetStateOutput, LegacyContract, Point, PointsAdded, Transfer};
+
+fn provider() -> JsonRpcClient<HttpTransport> {
+    JsonRpcClient::new(HttpTransport::new(
+        Url::parse("http://localhost:5050").unwrap(),
+    ))
+}
+
+#[test]
+fn test_legacy_pointer_arguments_folded() {
+    let account = SingleOwnerAccount::new(
+        provider(),
+        LocalWallet::from(SigningKey::from_secret_scalar(FieldElement::ONE)),
+        FieldElement::ONE,
+        FieldElement::TWO,
+        ExecutionEncoding::Legacy,
+    );
+    let contract = LegacyContract::new(FieldElement::THREE, account);
+
+    let points = vec![
+        Point {
+            x: FieldElement::ONE,
+            y: FieldElement::TWO,
+        },
+        Point {
+            x: FieldElement::THREE,
+            y: FieldElement::ONE,
+        },
+    ];
+
+    // `points_len` and `points` are folded into a single argument.
+    let call = contract.add_points_getcall(&points, &FieldElement::TWO);
+    assert_eq!(call.selector, selector!("add_points"));
+    assert_eq!(
+        call.calldata,
+        vec![
+            FieldElement::TWO,
+            FieldElement::ONE,
+            FieldElement::TWO,
+            FieldElement::THREE,
+            FieldElement::ONE,
+            FieldElement::TWO,
+        ]
+    );
+}
+
+#[test]
+fn test_legacy_multiple_outputs_struct() {
+    let felts = vec![
+        FieldElement::ONE,
+        FieldElement::TWO,
+        FieldElement::THREE,
+        FieldElement::ONE,
+        FieldElement::from(9_u32),
+    ];
+
+    assert_eq!(
+        GetStateOutput::deserialize(&felts, 0).unwrap(),
+        GetStateOutput {
+            owner: FieldElement::ONE,
+            origin: Point {
+                x: FieldElement::TWO,
+                y: FieldElement::THREE,
+            },
+            values: vec![FieldElement::from(9_u32)],
+        }
+    );
+}
+
+#[test]
+fn test_legacy_events() {
+    let to_emitted_event = |keys, data| EmittedEvent {
+        from_address: FieldElement::ONE,
+        keys,
+        data,
+        block_hash: FieldElement::TWO,
+        block_number: 1,
+        transaction_hash: FieldElement::THREE,
+    };
+
+    let transfer = to_emitted_event(
+        vec![selector!("Transfer")],
+        vec![
+            FieldElement::ONE,
+            FieldElement::TWO,
+            FieldElement::THREE,
+            FieldElement::ZERO,
+        ],
+    );
+
+    assert_eq!(
+        Event::try_from(transfer).unwrap(),
+        Event::Transfer(Transfer {
+            from_: FieldElement::ONE,
+            to: FieldElement::TWO,
+            value: 3_u32.into(),
+        })
+    );
+
+    let added = to_emitted_event(
+        vec![selector!("PointsAdded")],
+        vec![FieldElement::ONE, FieldElement::TWO, FieldElement::THREE],
+    );
+
+    assert_eq!(
+        Event::try_from(added).unwrap(),
+        Event::PointsAdded(PointsAdded {
+            points: vec![Point {
+                x: FieldElement::TWO,
+                y: FieldElement::THREE,
+            }],
+        })
+    );
+}
@@ -0,0 +1,39 @@
+//! L1 handlers messages generated by the `abigen` macro.
+use starknet::core::types::FieldElement;
+use starknet::macros::selector;
+use starknet::providers::{jsonrpc::HttpTransport, JsonRpcClient};
+use starknet_abigen::parser::cairo_types::{ContractAddress, EthAddress};
+use url::Url;
+
+mod bridge {
+    use starknet::core::types::FieldElement;
+    starknet_abigen::macros::abigen!(BridgeContract, "./tests/abis/l1_handler.json");
+}
+
+#[test]
+fn test_l1_handler_message() {
+    let provider = JsonRpcClient::new(HttpTransport::new(
+        Url::parse("http://localhost:5050").unwrap(),
+    ));
+    let reader = bridge::BridgeContractReader::new(FieldElement::TWO, &provider);
+
+    let account = ContractAddress(FieldElement::THREE);
+    let amount = 1000_u32.into();
+
+    // The `from_address` is not part of the payload.
+    let payload = reader.deposit_payload(&account, &amount);
+    assert_eq!(
+        payload,
+        vec![
+            FieldElement::THREE,
+            FieldElement::from(1000_u32),
+            FieldElement::ZERO
+        ]
+    );
+
+    let msg = reader.deposit_message(EthAddress(FieldElement::ONE), &account, &amount);
+    assert_eq!(msg.from_address, EthAddress(FieldElement::ONE));
+    assert_eq!(msg.to_address, FieldElement::TWO);
+    assert_eq!(msg.selector, selector!("deposit"));
+    assert_eq!(msg.payload, payload);
+}
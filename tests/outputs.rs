@@ -0,0 +1,27 @@
+//! Functions with several outputs generated by the `abigen` macro.
+use starknet::core::types::FieldElement;
+use starknet::providers::{jsonrpc::HttpTransport, JsonRpcClient};
+use starknet_abigen::parser::cairo_types::{Result, U256};
+use url::Url;
+
+mod outputs {
+    use starknet::core::types::FieldElement;
+    starknet_abigen::macros::abigen!(OutputsContract, "./tests/abis/outputs.json");
+}
+
+// Only checks the generated signature, no provider being available.
+#[allow(dead_code)]
+async fn get_position(
+    reader: &outputs::OutputsContractReader<'_, JsonRpcClient<HttpTransport>>,
+) -> Result<(FieldElement, U256, Vec<FieldElement>)> {
+    reader.get_position().await
+}
+
+#[test]
+fn test_multiple_outputs_reader() {
+    let provider = JsonRpcClient::new(HttpTransport::new(
+        Url::parse("http://localhost:5050").unwrap(),
+    ));
+    let reader = outputs::OutputsContractReader::new(FieldElement::ONE, &provider);
+    assert_eq!(reader.address, FieldElement::ONE);
+}
@@ -0,0 +1,206 @@
+//! Prebuilt bindings of the standard interfaces, enabled by features.
+//!
+//! Run with `cargo test --features standards`.
+
+#[cfg(feature = "erc20")]
+mod erc20 {
+    use starknet::core::types::{EmittedEvent, FieldElement};
+    use starknet::macros::selector;
+    use starknet_abigen::parser::cairo_types::ContractAddress;
+    use starknet_abigen::standards::erc20::{Event, Transfer};
+
+    #[test]
+    fn test_erc20_transfer_event() {
+        let emitted = EmittedEvent {
+            from_address: FieldElement::ONE,
+            keys: vec![selector!("Transfer"), FieldElement::ONE, FieldElement::TWO],
+            data: vec![FieldElement::from(1000_u32), FieldElement::ZERO],
+            block_hash: FieldElement::TWO,
+            block_number: 1,
+            transaction_hash: FieldElement::THREE,
+        };
+
+        assert_eq!(
+            Event::try_from(emitted).unwrap(),
+            Event::Transfer(Transfer {
+                from: ContractAddress(FieldElement::ONE),
+                to: ContractAddress(FieldElement::TWO),
+                value: 1000_u32.into(),
+            })
+        );
+    }
+}
+
+#[cfg(feature = "erc721")]
+mod erc721 {
+    use starknet::accounts::{Call, ExecutionEncoding, SingleOwnerAccount};
+    use starknet::core::types::FieldElement;
+    use starknet::macros::felt;
+    use starknet::macros::selector;
+    use starknet::providers::{jsonrpc::HttpTransport, JsonRpcClient};
+    use starknet::signers::{LocalWallet, SigningKey};
+    use starknet_abigen::parser::cairo_types::ContractAddress;
+    use starknet_abigen::standards::erc721::{IERC721CamelOnly, ERC721, IERC721, IERC721_ID};
+    use url::Url;
+
+    fn approve_call<T: IERC721>(token: &T, to: &ContractAddress) -> Call {
+        token.approve_getcall(to, &7_u32.into())
+    }
+
+    fn camel_transfer_call<T: IERC721CamelOnly>(token: &T, to: &ContractAddress) -> Call {
+        token.transferFrom_getcall(&ContractAddress(FieldElement::ONE), to, &7_u32.into())
+    }
+
+    #[test]
+    fn test_erc721_interface_id() {
+        assert_eq!(
+            IERC721_ID,
+            felt!("0x33eb2f84c309543403fd69f0d0f363781ef06ef6faeb0131ff16ea3175bd943")
+        );
+    }
+
+    #[test]
+    fn test_erc721_generic_over_interface() {
+        let account = SingleOwnerAccount::new(
+            JsonRpcClient::new(HttpTransport::new(
+                Url::parse("http://localhost:5050").unwrap(),
+            )),
+            LocalWallet::from(SigningKey::from_secret_scalar(FieldElement::ONE)),
+            FieldElement::ONE,
+            FieldElement::TWO,
+            ExecutionEncoding::Legacy,
+        );
+        let token = ERC721::new(FieldElement::THREE, account);
+
+        let call = approve_call(&token, &ContractAddress(FieldElement::TWO));
+        assert_eq!(call.selector, selector!("approve"));
+        assert_eq!(
+            call.calldata,
+            vec![
+                FieldElement::TWO,
+                FieldElement::from(7_u32),
+                FieldElement::ZERO
+            ]
+        );
+
+        let call = camel_transfer_call(&token, &ContractAddress(FieldElement::TWO));
+        assert_eq!(call.selector, selector!("transferFrom"));
+        assert_eq!(
+            call.calldata,
+            vec![
+                FieldElement::ONE,
+                FieldElement::TWO,
+                FieldElement::from(7_u32),
+                FieldElement::ZERO
+            ]
+        );
+    }
+}
+
+#[cfg(feature = "erc1155")]
+mod erc1155 {
+    use starknet::core::types::FieldElement;
+    use starknet_abigen::parser::EventDecodingMode;
+    use starknet_abigen::standards::erc1155::{Event, TransferBatch};
+
+    #[test]
+    fn test_erc1155_transfer_batch_round_trip() {
+        let ev = Event::TransferBatch(TransferBatch {
+            operator: FieldElement::ONE.into(),
+            from: FieldElement::TWO.into(),
+            to: FieldElement::THREE.into(),
+            ids: vec![1_u32.into(), 2_u32.into()],
+            values: vec![10_u32.into(), 20_u32.into()],
+        });
+
+        let (keys, data) = ev.encode();
+        assert_eq!(keys.len(), 4);
+        assert_eq!(data.len(), 10);
+        assert_eq!(
+            Event::decode(&keys, &data, EventDecodingMode::Strict).unwrap(),
+            ev
+        );
+    }
+}
+
+#[cfg(feature = "src6")]
+mod src6 {
+    use starknet::core::types::FieldElement;
+    use starknet::macros::felt;
+    use starknet::providers::{jsonrpc::HttpTransport, JsonRpcClient};
+    use starknet_abigen::parser::CairoType;
+    use starknet_abigen::standards::src6::{
+        Call, ISRC6CamelOnlyReader, ISRC6Reader, SRC6Reader, ISRC6_ID,
+    };
+    use url::Url;
+
+    fn is_src6<R: ISRC6Reader + ISRC6CamelOnlyReader>(_reader: &R) -> bool {
+        true
+    }
+
+    #[test]
+    fn test_src6_interface_id() {
+        assert_eq!(
+            ISRC6_ID,
+            felt!("0x2ceccef7f994940b3962a6c67e0ba4fcd37df7d131417c604f91e03caecc1cd")
+        );
+    }
+
+    #[test]
+    fn test_src6_reader_interfaces() {
+        let provider = JsonRpcClient::new(HttpTransport::new(
+            Url::parse("http://localhost:5050").unwrap(),
+        ));
+        let reader = SRC6Reader::new(FieldElement::ONE, &provider);
+        assert!(is_src6(&reader));
+    }
+
+    #[test]
+    fn test_src6_call_serialization() {
+        let call = Call {
+            to: FieldElement::ONE.into(),
+            selector: FieldElement::TWO,
+            calldata: vec![FieldElement::THREE],
+        };
+
+        assert_eq!(
+            Call::serialize(&call),
+            vec![
+                FieldElement::ONE,
+                FieldElement::TWO,
+                FieldElement::ONE,
+                FieldElement::THREE
+            ]
+        );
+    }
+}
+
+#[cfg(feature = "src5")]
+mod src5 {
+    use starknet::core::types::FieldElement;
+    use starknet::macros::felt;
+    use starknet::providers::{jsonrpc::HttpTransport, JsonRpcClient};
+    use starknet_abigen::standards::src5::{ISRC5Reader, SRC5Reader, ISRC5_ID};
+    use url::Url;
+
+    fn is_src5<R: ISRC5Reader>(_reader: &R) -> bool {
+        true
+    }
+
+    #[test]
+    fn test_src5_reader_interface() {
+        let provider = JsonRpcClient::new(HttpTransport::new(
+            Url::parse("http://localhost:5050").unwrap(),
+        ));
+        let reader = SRC5Reader::new(FieldElement::ONE, &provider);
+        assert!(is_src5(&reader));
+    }
+
+    #[test]
+    fn test_src5_interface_id() {
+        assert_eq!(
+            ISRC5_ID,
+            felt!("0x3f918d17e5ee77373b56385708f855659a07f75997f365cf87748628532a055")
+        );
+    }
+}